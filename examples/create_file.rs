@@ -1,5 +1,5 @@
 use failure::{format_err, Error};
-use hedera::{Client, SecretKey, Status};
+use hedera::{Client, NodeAddress, SecretKey, Status};
 use std::{env, thread::sleep, time::Duration};
 use std::str::FromStr;
 
@@ -10,8 +10,8 @@ async fn main() -> Result<(), Error> {
     // Operator is the account that sends the transaction to the network
     // This account is charged for the transaction fee
     let operator = "0:0:2".parse()?;
-    let client = Client::builder("testnet.hedera.com:50003")
-        .node("0:0:3".parse()?)
+    let client = Client::builder()
+        .node(NodeAddress::new("0:0:3".parse()?, "testnet.hedera.com:50003".parse()?))
         .operator(operator, || env::var("OPERATOR_SECRET"))
         .build()?;
 
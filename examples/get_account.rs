@@ -1,13 +1,13 @@
 use failure::Error;
-use hedera::Client;
+use hedera::{Client, NodeAddress};
 use std::env;
 
 fn main() -> Result<(), Error> {
     pretty_env_logger::try_init()?;
 
     let operator = env::var("OPERATOR")?.parse()?;
-    let client = Client::builder("testnet.hedera.com:50131")
-        .node("0:0:3".parse()?)
+    let client = Client::builder()
+        .node(NodeAddress::new("0:0:3".parse()?, "testnet.hedera.com:50131".parse()?))
         .operator(operator, || env::var("OPERATOR_SECRET"))
         .build()?;
 
@@ -1,5 +1,5 @@
 use failure::{format_err, Error};
-use hedera::{AccountId, Client, Status};
+use hedera::{AccountId, Client, NodeAddress, Status};
 use std::{env, thread::sleep, time::Duration};
 
 #[tokio::main]
@@ -9,13 +9,13 @@ async fn main() -> Result<(), Error> {
     // Operator is the account that sends the transaction to the network
     // This account is charged for the transaction fee
     let operator = env::var("OPERATOR")?.parse()?;
-    let client = Client::builder("testnet.hedera.com:50131")
-        .node("0:0:3".parse()?)
+    let client = Client::builder()
+        .node(NodeAddress::new("0:0:3".parse()?, "testnet.hedera.com:50131".parse()?))
         .operator(operator, || env::var("OPERATOR_SECRET"))
         .build()?;
 
     // Receiver is the account that receives the transferred crypto
-    let receiver: AccountId = "0:0:2".parse()?;
+    let receiver = AccountId::with_default_realm(2, &client);
 
     // transfer 1 hbar from the operator account to the receiver account.
     let id = client
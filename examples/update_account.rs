@@ -1,6 +1,6 @@
 use failure::{format_err, Error};
 use futures::FutureExt;
-use hedera::{Client, Status};
+use hedera::{AccountId, Client, NodeAddress, Status};
 use std::{env, thread::sleep, time::Duration};
 
 #[tokio::main]
@@ -10,8 +10,8 @@ async fn main() -> Result<(), Error> {
     // Operator is the account that sends the transaction to the network
     // This account is charged for the transaction fee
     let operator = "0:0:1010".parse()?;
-    let client = Client::builder("testnet.hedera.com:50003")
-        .node("0:0:3".parse()?)
+    let client = Client::builder()
+        .node(NodeAddress::new("0:0:3".parse()?, "testnet.hedera.com:50003".parse()?))
         .operator(operator, || env::var("OPERATOR_SECRET"))
         .build()?;
 
@@ -21,8 +21,8 @@ async fn main() -> Result<(), Error> {
         .update_account(operator)
         .send_record_threshold(1000005)
         .receive_record_threshold(2000005)
-        .proxy_account("0:0:3".parse()?)
-        .auto_renew_period(Duration::from_secs(1000))
+        .proxy_account(AccountId::with_default_realm(3, &client))
+        .auto_renew_period(Duration::from_secs(7_890_000))
         // .expires_at(expiration: DateTime<Utc>)
         .expires_in(Duration::from_secs(2_592_000))
         .sign(&env::var("OPERATOR_SECRET")?.parse()?) // sign as the owner of the account to approve the change
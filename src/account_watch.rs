@@ -0,0 +1,150 @@
+use crate::{query::QueryCryptoGetInfo, AccountId, AccountInfo, Client, PublicKey};
+use chrono::{DateTime, Utc};
+use failure::Error;
+use futures::{stream, Stream};
+use std::time::Duration;
+
+/// The info of an account polled by `PartialAccountMessage::watch_info`, with what changed
+/// since the info before it.
+#[derive(Debug)]
+pub struct AccountSnapshot {
+    pub info: AccountInfo,
+
+    /// `None` for the first snapshot.
+    pub delta: Option<AccountInfoDelta>,
+}
+
+/// What changed in an account between two snapshots.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccountInfoDelta {
+    /// The change in balance, in tinybars.
+    pub balance: i64,
+
+    /// The new key, if it changed.
+    pub key: Option<PublicKey>,
+
+    /// The new expiration time, if it changed.
+    pub expiration_time: Option<DateTime<Utc>>,
+}
+
+impl AccountInfoDelta {
+    /// Did nothing watched change?
+    pub fn is_empty(&self) -> bool {
+        self.balance == 0 && self.key.is_none() && self.expiration_time.is_none()
+    }
+}
+
+// What a delta is computed from
+struct Watched {
+    balance: u64,
+    key: PublicKey,
+    expiration_time: DateTime<Utc>,
+}
+
+impl Watched {
+    fn of(info: &AccountInfo) -> Self {
+        Self {
+            balance: info.balance,
+            key: info.key.clone(),
+            expiration_time: info.expiration_time,
+        }
+    }
+
+    #[allow(clippy::cast_possible_wrap)]
+    fn delta(&self, info: &AccountInfo) -> AccountInfoDelta {
+        AccountInfoDelta {
+            balance: info.balance.wrapping_sub(self.balance) as i64,
+            key: Some(info.key.clone()).filter(|key| *key != self.key),
+            expiration_time: Some(info.expiration_time)
+                .filter(|time| *time != self.expiration_time),
+        }
+    }
+}
+
+struct Watch {
+    client: Client,
+    account: AccountId,
+    interval: Duration,
+    polled: bool,
+    last: Option<Watched>,
+}
+
+pub(crate) fn watch(
+    client: &Client,
+    account: AccountId,
+    interval: Duration,
+) -> impl Stream<Item = Result<AccountSnapshot, Error>> {
+    let watch = Watch {
+        client: client.clone(),
+        account,
+        interval,
+        polled: false,
+        last: None,
+    };
+
+    stream::unfold(watch, |mut watch| {
+        async move {
+            // the first snapshot is taken right away
+            if watch.polled {
+                watch.client.clock.sleep(watch.interval).await;
+            }
+
+            watch.polled = true;
+
+            let info = QueryCryptoGetInfo::new(&watch.client, watch.account)
+                .get_async()
+                .await;
+
+            let snapshot = info.map(|info| {
+                let delta = watch.last.as_ref().map(|last| last.delta(&info));
+                watch.last = Some(Watched::of(&info));
+
+                AccountSnapshot { info, delta }
+            });
+
+            Some((snapshot, watch))
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Watched;
+    use crate::{AccountId, AccountInfo, SecretKey};
+    use chrono::{Duration as ChronoDuration, Utc};
+    use std::time::Duration;
+
+    fn info(balance: u64) -> AccountInfo {
+        AccountInfo {
+            account_id: AccountId::new(0, 0, 1001),
+            contract_account_id: String::new(),
+            deleted: false,
+            proxy_account_id: None,
+            proxy_received: 0,
+            key: SecretKey::generate("").0.public(),
+            balance,
+            generate_send_record_threshold: 0,
+            generate_receive_record_threshold: 0,
+            receiver_signature_required: false,
+            expiration_time: Utc::now(),
+            auto_renew_period: Duration::from_secs(7_890_000),
+            claims: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_delta() {
+        let before = info(500);
+        let mut after = info(200);
+        after.key = before.key.clone();
+        after.expiration_time = before.expiration_time + ChronoDuration::days(90);
+
+        let delta = Watched::of(&before).delta(&after);
+
+        assert_eq!(delta.balance, -300);
+        assert_eq!(delta.key, None);
+        assert_eq!(delta.expiration_time, Some(after.expiration_time));
+        assert!(!delta.is_empty());
+        assert!(Watched::of(&after).delta(&after).is_empty());
+    }
+}
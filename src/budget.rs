@@ -0,0 +1,111 @@
+use crate::ErrorKind;
+use failure::Error;
+use parking_lot::Mutex;
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// Limits shared by every request of a workflow (e.g. upload a file, create a contract from
+/// it and call the contract), so that the workflow as a whole fails fast instead of each
+/// request using up its own retries.
+///
+/// Clones share what has been spent. Attach a budget with `Client::with_budget` or with
+/// `budget` on a single transaction or query.
+#[derive(Debug, Clone, Default)]
+pub struct Budget(Arc<Mutex<State>>);
+
+#[derive(Debug, Default)]
+struct State {
+    max_attempts: Option<usize>,
+    deadline: Option<Instant>,
+    max_fees: Option<u64>,
+    attempts: usize,
+    fees: u64,
+}
+
+impl Budget {
+    /// A budget without limits; add them with `max_attempts`, `max_time` and `max_fees`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of times requests may be sent, including retries.
+    pub fn max_attempts(self, attempts: usize) -> Self {
+        self.0.lock().max_attempts = Some(attempts);
+        self
+    }
+
+    /// How long from now requests may be sent for.
+    pub fn max_time(self, time: Duration) -> Self {
+        self.0.lock().deadline = Some(Instant::now() + time);
+        self
+    }
+
+    /// The total, in tinybar, of the maximum fees of transactions and the payments of queries.
+    pub fn max_fees(self, tinybar: u64) -> Self {
+        self.0.lock().max_fees = Some(tinybar);
+        self
+    }
+
+    /// The number of times requests have been sent.
+    pub fn attempts(&self) -> usize {
+        self.0.lock().attempts
+    }
+
+    /// The fees committed so far, in tinybar. The fees actually charged may be lower.
+    pub fn fees(&self) -> u64 {
+        self.0.lock().fees
+    }
+
+    // Count one more request sent, failing if that is over the budget
+    pub(crate) fn spend_attempt(&self) -> Result<(), Error> {
+        let mut state = self.0.lock();
+
+        if state.deadline.map_or(false, |deadline| Instant::now() >= deadline) {
+            Err(ErrorKind::BudgetExhausted("time"))?;
+        }
+
+        if state.max_attempts.map_or(false, |max| state.attempts >= max) {
+            Err(ErrorKind::BudgetExhausted("attempts"))?;
+        }
+
+        state.attempts += 1;
+
+        Ok(())
+    }
+
+    // Commit to paying up to `fee`, failing if that is over the budget
+    pub(crate) fn spend_fee(&self, fee: u64) -> Result<(), Error> {
+        let mut state = self.0.lock();
+        let fees = state.fees.saturating_add(fee);
+
+        if state.max_fees.map_or(false, |max| fees > max) {
+            Err(ErrorKind::BudgetExhausted("fees"))?;
+        }
+
+        state.fees = fees;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Budget;
+
+    #[test]
+    fn test_shared_between_clones() {
+        let budget = Budget::new().max_attempts(2).max_fees(100);
+        let step = budget.clone();
+
+        assert!(step.spend_attempt().is_ok());
+        assert!(budget.spend_attempt().is_ok());
+        assert!(step.spend_attempt().is_err());
+
+        assert!(step.spend_fee(60).is_ok());
+        assert!(budget.spend_fee(60).is_err());
+        assert_eq!(budget.fees(), 60);
+        assert_eq!(budget.attempts(), 2);
+    }
+}
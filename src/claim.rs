@@ -4,16 +4,52 @@ use crate::{
     AccountId,
 };
 use failure::Error;
-use protobuf::RepeatedField;
+use protobuf::{parse_from_bytes, Message, RepeatedField};
 use try_from::{TryFrom, TryInto};
 
-#[derive(Debug, Clone)]
+/// A hash attached to an account, together with the keys that may delete it.
+#[derive(Debug, Clone, PartialEq)]
 pub struct Claim {
     pub account: AccountId,
     pub hash: Vec<u8>,
     pub keys: Vec<PublicKey>,
 }
 
+impl Claim {
+    pub fn builder(account: AccountId, hash: impl Into<Vec<u8>>) -> ClaimBuilder {
+        ClaimBuilder(Self {
+            account,
+            hash: hash.into(),
+            keys: Vec::new(),
+        })
+    }
+
+    /// Serialize the claim as a protobuf `Claim` message.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        Ok(self.to_proto()?.write_to_bytes()?)
+    }
+
+    /// Parse a serialized protobuf `Claim` message.
+    pub fn from_bytes(bytes: impl AsRef<[u8]>) -> Result<Self, Error> {
+        let claim: proto::CryptoAddClaim::Claim = parse_from_bytes(bytes.as_ref())?;
+        claim.try_into()
+    }
+}
+
+pub struct ClaimBuilder(Claim);
+
+impl ClaimBuilder {
+    /// Add a key that must sign to delete the claim.
+    pub fn key(mut self, key: PublicKey) -> Self {
+        self.0.keys.push(key);
+        self
+    }
+
+    pub fn build(self) -> Claim {
+        self.0
+    }
+}
+
 impl TryFrom<proto::CryptoAddClaim::Claim> for Claim {
     type Err = Error;
 
@@ -49,3 +85,32 @@ impl ToProto<proto::CryptoAddClaim::Claim> for Claim {
         Ok(claim)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Claim;
+    use crate::{proto::ToProto, AccountId, SecretKey};
+    use failure::Error;
+    use try_from::TryInto;
+
+    #[test]
+    fn test_proto_round_trip() -> Result<(), Error> {
+        let (secret1, _) = SecretKey::generate("");
+        let (secret2, _) = SecretKey::generate("");
+
+        let claim = Claim::builder(AccountId::new(0, 0, 1001), vec![0xab; 48])
+            .key(secret1.public())
+            .key(secret2.public())
+            .build();
+
+        let proto = claim.to_proto()?;
+        assert_eq!(proto.get_keys().get_keys().len(), 2);
+
+        let decoded: Claim = proto.try_into()?;
+        assert_eq!(decoded, claim);
+
+        assert_eq!(Claim::from_bytes(claim.to_bytes()?)?, claim);
+
+        Ok(())
+    }
+}
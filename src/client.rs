@@ -1,51 +1,173 @@
 use crate::{
-    crypto::SecretKey,
+    account_watch::{self, AccountSnapshot},
+    clock::{self, Clock, SharedClock},
+    crypto::{PublicKey, SecretKey},
     id::{ContractId, FileId},
-    proto::{
-        CryptoService_grpc::CryptoServiceClient, FileService_grpc::FileServiceClient,
-        SmartContractService_grpc::SmartContractServiceClient,
-    },
+    fee_policy::{FeeDecision, FeePolicy},
+    network::{self, ConnectionConfig, Network},
+    node_address::named_network,
+    notarization::{self, Notarization},
+    payload_log::PayloadLogging,
+    routing::{Routes, Service},
     query::{
-        Query, QueryCryptoGetAccountBalance, QueryCryptoGetClaim, QueryCryptoGetInfo,
-        QueryFileGetContents, QueryFileGetInfo, QueryTransactionGetReceipt,
-        QueryTransactionGetRecord,
+        Query, QueryBody, QueryContractCallLocal, QueryContractGetBytecode, QueryContractGetInfo,
+        QueryCryptoGetAccountBalance, QueryCryptoGetAccountRecords, QueryCryptoGetClaim,
+        QueryCryptoGetInfo, QueryFileGetContents, QueryFileGetInfo, QueryResponse,
+        QueryTransactionGetReceipt, QueryTransactionGetRecord,
     },
     transaction::{
-        Transaction, TransactionContractCall, TransactionContractCreate, TransactionContractUpdate,
-        TransactionContractDelete, TransactionCryptoCreate, TransactionCryptoDelete,
+        self, Transaction, TransactionAdminDelete, TransactionAdminRecover, TransactionContractCall,
+        TransactionContractCreate, TransactionContractUpdate, TransactionContractDelete,
+        TransactionCryptoAddClaim, TransactionCryptoCreate, TransactionCryptoDelete,
         TransactionCryptoDeleteClaim, TransactionCryptoTransfer, TransactionCryptoUpdate,
-        TransactionFileAppend, TransactionFileCreate, TransactionFileDelete,
+        TransactionFileAppend, TransactionFileCreate, TransactionFileDelete, TransactionFileUpdate,
+        TransactionFreeze, TransactionRaw,
     },
-    AccountId, TransactionId,
+    AccountId, Budget, Claim, ClientStats, ContractCreateFlow, ErrorKind, File, NodeAddress,
+    NodeHealth, Signature, Status, TransactionId, TransactionReceipt,
 };
-use failure::{err_msg, format_err, Error};
-use grpc::ClientStub;
-use itertools::Itertools;
-use std::{fmt, sync::Arc, time::Duration};
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use failure::{err_msg, Error};
+use futures::{stream, Future, Stream, StreamExt};
+use std::{collections::HashMap, env, fmt, sync::Arc, time::Duration};
 use try_from::TryInto;
 
-pub struct ClientBuilder<'a> {
-    address: &'a str,
+#[cfg(feature = "proto")]
+use crate::{proto::TransactionBody::TransactionBody_oneof_data, transaction::TransactionCustom};
+
+pub(crate) type OperatorSecret = Arc<dyn Fn() -> Result<SecretKey, Error> + Send + Sync>;
+
+pub struct ClientBuilder {
+    nodes: Vec<NodeAddress>,
+    config: ConnectionConfig,
     node: Option<AccountId>,
+    routes: Routes,
     operator: Option<AccountId>,
-    operator_secret: Option<Arc<dyn Fn() -> Result<SecretKey, Error> + Send + Sync>>,
+    operator_secret: Option<OperatorSecret>,
+    operators: HashMap<String, (AccountId, OperatorSecret)>,
+    fee_policy: Option<FeePolicy>,
+    shard: i64,
+    realm: i64,
+    clock: SharedClock,
+    payload_logging: PayloadLogging,
+    strict_payments: bool,
 }
 
+// How long the file holding the hash of a notarized payload is kept; it can be extended
+// with `PartialFileMessage::extend_expiration`
+const NOTARIZATION_LIFETIME: Duration = Duration::from_secs(2_592_000);
+
+// The most file contents `PartialFileMessage::append_chunked` puts in one append, leaving
+// room for the rest of the transaction and its signatures under the 6 KiB limit
+const FILE_APPEND_CHUNK_SIZE: usize = 4096;
+
+// How many balance queries `Client::balances` keeps in flight at once
+const MAX_CONCURRENT_BALANCE_QUERIES: usize = 8;
+
+// How many transactions `Client::receipts` waits on at once
+const MAX_CONCURRENT_RECEIPT_QUERIES: usize = 8;
+
+// How long after a transaction can no longer reach consensus its receipt is still polled for
+const RECEIPT_DELAY: Duration = Duration::from_secs(10);
+
+#[derive(Clone)]
 pub struct Client {
     pub(crate) node: Option<AccountId>,
+    pub(crate) routes: Arc<Routes>,
     pub(crate) operator: Option<AccountId>,
-    pub(crate) operator_secret: Option<Arc<dyn Fn() -> Result<SecretKey, Error> + Send + Sync>>,
-    pub(crate) crypto: Arc<CryptoServiceClient>,
-    pub(crate) file: Arc<FileServiceClient>,
-    pub(crate) contract: Arc<SmartContractServiceClient>,
+    pub(crate) operator_secret: Option<OperatorSecret>,
+    pub(crate) operators: Arc<HashMap<String, (AccountId, OperatorSecret)>>,
+    pub(crate) fee_policy: Option<FeePolicy>,
+    pub(crate) budget: Option<Budget>,
+    pub(crate) shard: i64,
+    pub(crate) realm: i64,
+    pub(crate) clock: SharedClock,
+    pub(crate) network: Arc<Network>,
 }
 
-impl<'a> ClientBuilder<'a> {
-    pub fn node(mut self, node: AccountId) -> Self {
+impl ClientBuilder {
+    /// Add a node that requests can be sent to. The first node added is used by default,
+    /// unless it has been failing recent requests.
+    pub fn node(mut self, address: NodeAddress) -> Self {
+        self.nodes.push(address);
+        self
+    }
+
+    /// Add every node of a network address book.
+    pub fn nodes(mut self, addresses: impl IntoIterator<Item = NodeAddress>) -> Self {
+        self.nodes.extend(addresses);
+        self
+    }
+
+    /// How long to wait for a connection to a node to be established. Defaults to 5 seconds.
+    pub fn connection_timeout(mut self, timeout: Duration) -> Self {
+        self.config.connection_timeout = timeout;
+        self
+    }
+
+    /// Re-establish connections that have been idle for longer than `interval`
+    /// before sending the next request on them, as idle connections are often dropped
+    /// by the other end or by a proxy in between. No keepalive pings are sent.
+    pub fn idle_reconnect_interval(mut self, interval: Duration) -> Self {
+        self.config.idle_reconnect_interval = Some(interval);
+        self
+    }
+
+    /// The largest HTTP/2 frame payload, in bytes, the client is willing to receive.
+    pub fn max_frame_size(mut self, size: u32) -> Self {
+        self.config.max_frame_size = Some(size);
+        self
+    }
+
+    /// Set `TCP_NODELAY` on connections. Defaults to `true`.
+    pub fn no_delay(mut self, no_delay: bool) -> Self {
+        self.config.no_delay = no_delay;
+        self
+    }
+
+    /// How much of each request and response to log at the `trace` level; defaults to the
+    /// whole message. `PayloadLogging::Summary` keeps contents and keys out of the logs.
+    pub fn payload_logging(mut self, logging: PayloadLogging) -> Self {
+        self.payload_logging = logging;
+        self
+    }
+
+    /// Never pay for anything the caller did not explicitly pay for: paid queries are not
+    /// given a payment from the operator, and fail with `ErrorKind::PaymentRequired` and
+    /// their cost unless one is set with `Query::payment`. Defaults to `false`.
+    ///
+    /// Asking for the cost still sends a payment from the operator, as nodes require one,
+    /// but cost queries are not charged.
+    pub fn strict_payments(mut self, strict: bool) -> Self {
+        self.strict_payments = strict;
+        self
+    }
+
+    /// Send requests to this node unless one is set on the request itself.
+    pub fn default_node(mut self, node: AccountId) -> Self {
         self.node = Some(node);
         self
     }
 
+    /// Send queries to this node instead of the default node.
+    pub fn query_node(mut self, node: AccountId) -> Self {
+        self.routes.queries = Some(node);
+        self
+    }
+
+    /// Send transactions to this node instead of the default node.
+    pub fn transaction_node(mut self, node: AccountId) -> Self {
+        self.routes.transactions = Some(node);
+        self
+    }
+
+    /// Send both queries and transactions of `service` to this node. Takes precedence
+    /// over `query_node` and `transaction_node`.
+    pub fn service_node(mut self, service: Service, node: AccountId) -> Self {
+        self.routes.services.insert(service, node);
+        self
+    }
+
     pub fn operator<R, E>(
         mut self,
         operator: AccountId,
@@ -61,8 +183,72 @@ impl<'a> ClientBuilder<'a> {
         self
     }
 
+    /// Register another operator under `name`, to act as through `Client::with_operator`.
+    pub fn named_operator<R, E>(
+        mut self,
+        name: impl Into<String>,
+        operator: AccountId,
+        secret: impl Fn() -> R + Send + Sync + 'static,
+    ) -> Self
+    where
+        E: fmt::Debug + fmt::Display + Send + Sync + 'static,
+        R: TryInto<SecretKey, Err = E>,
+    {
+        let secret: OperatorSecret = Arc::new(move || secret().try_into().map_err(err_msg));
+        self.operators.insert(name.into(), (operator, secret));
+
+        self
+    }
+
+    /// The shard and realm of the network. Defaults to shard 0, realm 0.
+    ///
+    /// Transactions that refer to an account, file or contract outside of them fail to build.
+    pub fn shard_realm(mut self, shard: i64, realm: i64) -> Self {
+        self.shard = shard;
+        self.realm = realm;
+        self
+    }
+
+    /// Decide, for each transaction made through the client, whether it may be submitted
+    /// with the fee it was given, given its kind (e.g. `CryptoTransfer`) and that fee.
+    ///
+    /// The policy runs when the transaction is built, before it is signed; capping the fee
+    /// lowers the maximum fee of the transaction. A transaction loaded with
+    /// `Transaction::from_bytes` is already signed, so the policy runs when it is executed
+    /// instead, and a cap below its fee rejects it.
+    pub fn fee_policy(
+        mut self,
+        policy: impl Fn(&str, u64) -> FeeDecision + Send + Sync + 'static,
+    ) -> Self {
+        self.fee_policy = Some(Arc::new(policy));
+        self
+    }
+
+    /// Read the time, and wait between retries, with `clock` instead of the system clock;
+    /// e.g. to test how requests behave as their valid window or an expiration passes.
+    pub fn clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
     pub fn build(self) -> Result<Client, Error> {
-        let mut client = Client::new(&self.address)?;
+        let mut network = Network::connect(self.nodes, self.config)?;
+        network.payload_logging = self.payload_logging;
+        network.strict_payments = self.strict_payments;
+
+        let mut client = Client {
+            node: None,
+            routes: Arc::new(self.routes),
+            operator: None,
+            operator_secret: None,
+            operators: Arc::new(self.operators),
+            fee_policy: self.fee_policy,
+            budget: None,
+            shard: self.shard,
+            realm: self.realm,
+            clock: self.clock,
+            network: Arc::new(network),
+        };
 
         if let Some(node) = self.node {
             client.set_node(node);
@@ -78,58 +264,171 @@ impl<'a> ClientBuilder<'a> {
 }
 
 impl Client {
-    pub fn builder(address: &str) -> ClientBuilder {
+    pub fn builder() -> ClientBuilder {
         ClientBuilder {
-            address,
+            nodes: Vec::new(),
+            config: ConnectionConfig::default(),
             node: None,
+            routes: Routes::default(),
             operator: None,
             operator_secret: None,
+            operators: HashMap::new(),
+            fee_policy: None,
+            shard: 0,
+            realm: 0,
+            clock: clock::system(),
+            payload_logging: PayloadLogging::default(),
+            strict_payments: false,
         }
     }
 
-    pub fn new(address: impl AsRef<str>) -> Result<Self, Error> {
-        let address = address.as_ref();
-        let (host, port) = address.split(':').next_tuple().ok_or_else(|| {
-            format_err!("failed to parse 'host:port' from address: {:?}", address)
-        })?;
+    /// Connect to the given nodes. The first node that has not been failing recent requests
+    /// is used unless another is set with `set_node` or on the request itself.
+    pub fn new(nodes: impl IntoIterator<Item = NodeAddress>) -> Result<Self, Error> {
+        Self::builder().nodes(nodes).build()
+    }
 
-        let port = port.parse()?;
+    /// Configure a client from the environment, with the variables shared by the Hedera SDKs:
+    ///
+    /// * `HEDERA_NETWORK`: `mainnet`, `testnet`, `previewnet`, or a comma-separated list of
+    ///   nodes as `{account}@{host}:{port}`
+    /// * `HEDERA_OPERATOR_ID` and `HEDERA_OPERATOR_KEY`: the operator account and its secret
+    ///   key, if transactions and paid queries are to be made
+    /// * `HEDERA_NODE` (optional): the account of the node to use by default
+    pub fn from_env() -> Result<Self, Error> {
+        let network =
+            env::var("HEDERA_NETWORK").map_err(|_| ErrorKind::MissingField("HEDERA_NETWORK"))?;
 
-        let inner = Arc::new(grpc::Client::new_plain(
-            &host,
-            port,
-            grpc::ClientConf {
-                http: httpbis::ClientConf {
-                    no_delay: Some(true),
-                    connection_timeout: Some(Duration::from_secs(5)),
-                    ..httpbis::ClientConf::default()
-                },
-            },
-        )?);
-
-        let crypto = Arc::new(CryptoServiceClient::with_client(inner.clone()));
-        let file = Arc::new(FileServiceClient::with_client(inner.clone()));
-        let contract = Arc::new(SmartContractServiceClient::with_client(inner.clone()));
-
-        // Default the node to what we know every testnet is on
-        let node = if address.starts_with("testnet.") {
-            Some(AccountId {
-                shard: 0,
-                realm: 0,
-                account: 3,
-            })
-        } else {
-            None
+        let nodes = match named_network(network.trim()) {
+            Some(nodes) => nodes,
+            None => network
+                .split(',')
+                .map(|node| node.trim().parse())
+                .collect::<Result<_, Error>>()?,
         };
 
-        Ok(Self {
-            node,
-            operator: None,
-            operator_secret: None,
-            crypto,
-            file,
-            contract,
-        })
+        let mut builder = Self::builder().nodes(nodes);
+
+        if let Ok(node) = env::var("HEDERA_NODE") {
+            builder = builder.default_node(node.trim().parse()?);
+        }
+
+        if let Ok(operator) = env::var("HEDERA_OPERATOR_ID") {
+            let secret = env::var("HEDERA_OPERATOR_KEY")
+                .map_err(|_| ErrorKind::MissingField("HEDERA_OPERATOR_KEY"))?;
+
+            // fail early (rather than on the first transaction) if the key is invalid
+            secret.parse::<SecretKey>()?;
+
+            builder = builder.operator(operator.trim().parse()?, move || {
+                secret.parse::<SecretKey>()
+            });
+        }
+
+        builder.build()
+    }
+
+    /// The shard of the network, for IDs created with `with_default_realm`.
+    #[inline]
+    pub fn shard(&self) -> i64 {
+        self.shard
+    }
+
+    /// The realm of the network, for IDs created with `with_default_realm`.
+    #[inline]
+    pub fn realm(&self) -> i64 {
+        self.realm
+    }
+
+    /// The account that pays for transactions and queries made through this client.
+    #[inline]
+    pub fn operator_id(&self) -> Option<AccountId> {
+        self.operator
+    }
+
+    /// The public key of the operator, derived from the configured operator secret.
+    pub fn operator_public_key(&self) -> Result<Option<PublicKey>, Error> {
+        match &self.operator_secret {
+            Some(secret) => Ok(Some(secret()?.public())),
+            None => Ok(None),
+        }
+    }
+
+    /// Check that the operator secret is the secret of the key of the operator account, to
+    /// catch a misconfigured client before its first transaction fails. Fails with
+    /// `OperatorKeyMismatch` if it is not.
+    ///
+    /// This fetches the info of the account with a query paid for by the operator; a node
+    /// that refuses the payment because of a bad signature is taken as a mismatch too.
+    pub fn verify_operator(&self) -> Result<(), Error> {
+        crate::RUNTIME
+            .lock()
+            .block_on(self.verify_operator_async())
+    }
+
+    pub fn verify_operator_async(&self) -> impl Future<Output = Result<(), Error>> {
+        let operator = self.operator;
+        let public = self.operator_public_key();
+        let info = operator.map(|operator| self.account(operator).info());
+
+        async move {
+            let (operator, mut info) = match (operator, info) {
+                (Some(operator), Some(info)) => (operator, info),
+                _ => Err(ErrorKind::MissingField("operator"))?,
+            };
+
+            let public = public?.ok_or(ErrorKind::MissingField("operator"))?;
+
+            let matches = match info.get_async().await {
+                Ok(info) => info.key == public,
+
+                Err(error) => match error.downcast_ref() {
+                    Some(ErrorKind::PreCheck(Status::InvalidSignature)) => false,
+                    _ => return Err(error),
+                },
+            };
+
+            if !matches {
+                Err(ErrorKind::OperatorKeyMismatch {
+                    operator,
+                    key: public,
+                })?;
+            }
+
+            Ok(())
+        }
+    }
+
+    /// Stop the client (and every clone of it) for an orderly restart.
+    ///
+    /// New transactions and queries fail with `ErrorKind::ClientShutdown`. The ones in
+    /// flight, including their retries, are given up to `timeout` to finish before the
+    /// connections to the nodes are closed.
+    pub fn shutdown(&self, timeout: Duration) -> Result<(), Error> {
+        self.network.shutdown(timeout)
+    }
+
+    /// The node that transactions and queries are sent to by default.
+    ///
+    /// Unless set explicitly, this is the first node that has not been failing recent requests.
+    #[inline]
+    pub fn node_id(&self) -> AccountId {
+        self.default_node()
+    }
+
+    /// How each node has been responding to recent requests.
+    pub fn node_health(&self) -> Vec<NodeHealth> {
+        self.network.health()
+    }
+
+    /// The pre-check and receipt statuses, retries and fees of the requests made through
+    /// this client and its clones since it was built.
+    pub fn stats(&self) -> ClientStats {
+        self.network.stats.lock().clone()
+    }
+
+    pub(crate) fn default_node(&self) -> AccountId {
+        self.node.unwrap_or_else(|| self.network.default_node())
     }
 
     #[inline]
@@ -150,11 +449,87 @@ impl Client {
         self.operator_secret = Some(Arc::new(move || secret().try_into().map_err(err_msg)));
     }
 
+    /// Register another operator under `name`, to act as through `with_operator`.
+    ///
+    /// Only this client, and clones made from it afterwards, know the operator.
+    pub fn set_named_operator<R, E>(
+        &mut self,
+        name: impl Into<String>,
+        operator: AccountId,
+        secret: impl Fn() -> R + Send + Sync + 'static,
+    ) where
+        E: fmt::Debug + fmt::Display + Send + Sync + 'static,
+        R: TryInto<SecretKey, Err = E>,
+    {
+        let secret: OperatorSecret = Arc::new(move || secret().try_into().map_err(err_msg));
+        Arc::make_mut(&mut self.operators).insert(name.into(), (operator, secret));
+    }
+
+    /// A client whose transactions and queries all count against `budget`, e.g. for the
+    /// steps of one workflow. It shares the connections to the network of this client.
+    pub fn with_budget(&self, budget: &Budget) -> Client {
+        let mut client = self.clone();
+        client.budget = Some(budget.clone());
+        client
+    }
+
+    /// A client that pays for and signs transactions as the operator registered under
+    /// `name`. It shares the connections to the network of this client.
+    pub fn with_operator(&self, name: &str) -> Result<Client, Error> {
+        let (operator, secret) = self
+            .operators
+            .get(name)
+            .ok_or_else(|| ErrorKind::UnknownOperator(name.to_owned()))?;
+
+        let mut client = self.clone();
+        client.operator = Some(*operator);
+        client.operator_secret = Some(secret.clone());
+
+        Ok(client)
+    }
+
     #[inline]
     pub fn transfer_crypto(&self) -> Transaction<TransactionCryptoTransfer> {
         TransactionCryptoTransfer::new(self)
     }
 
+    /// Transfer `amount` tinybars from `from` to `to`, signed by the operator.
+    ///
+    /// Returns once the transfer passed pre-check; its receipt shows whether it succeeded.
+    /// If `from` is not the operator, its key must sign too; build the transfer with
+    /// `transfer_crypto` to add that signature.
+    ///
+    /// Fails with `ErrorKind::InvalidTransferList` unless `amount` is positive.
+    pub fn send(
+        &self,
+        from: AccountId,
+        to: AccountId,
+        amount: i64,
+    ) -> Result<TransactionId, Error> {
+        crate::RUNTIME
+            .lock()
+            .block_on(self.send_async(from, to, amount))
+    }
+
+    pub fn send_async(
+        &self,
+        from: AccountId,
+        to: AccountId,
+        amount: i64,
+    ) -> impl Future<Output = Result<TransactionId, Error>> {
+        let mut transfer = TransactionCryptoTransfer::new(self);
+
+        async move {
+            // a negative amount would send from `to` instead
+            let debit = amount.checked_neg().filter(|_| amount > 0).ok_or_else(|| {
+                ErrorKind::InvalidTransferList(format!("cannot send {} tinybars", amount))
+            })?;
+
+            transfer.transfer(from, debit).transfer(to, amount);
+            transfer.execute_async().await
+        }
+    }
+
     /// Create a new account. After the account is created, the AccountID for it is in the
     /// receipt, or can be retrieved with a GetByKey query, or by asking for a Record of the
     /// transaction to be created, and retrieving that.
@@ -163,23 +538,132 @@ impl Client {
         TransactionCryptoCreate::new(self)
     }
 
+    /// Prepare a transaction creating an account for `key`, without signing it.
+    ///
+    /// The transaction is paid for by the operator, but the operator secret is not used;
+    /// sign `body_bytes()` with the operator key wherever it is held (e.g. an HSM) and pass
+    /// the signature to `finalize_create_account`. The key of the new account does not
+    /// need to sign.
+    pub fn create_account_unsigned(
+        &self,
+        key: PublicKey,
+        initial_balance: u64,
+    ) -> Transaction<TransactionCryptoCreate, TransactionRaw> {
+        let mut tx = self.create_account();
+        tx.key(key).initial_balance(initial_balance);
+
+        if let Some(operator) = self.operator {
+            // resets the operator secret so it is not used to sign
+            tx.operator(operator);
+        }
+
+        tx.freeze()
+    }
+
+    /// Attach the operator signature to a transaction from `create_account_unsigned`,
+    /// execute it and wait for the ID of the new account.
+    pub fn finalize_create_account(
+        &self,
+        mut tx: Transaction<TransactionCryptoCreate, TransactionRaw>,
+        operator_key: &PublicKey,
+        signature: &Signature,
+    ) -> Result<AccountId, Error> {
+        let id = tx.add_signature(operator_key, signature)?.execute()?;
+        let receipt = self.transaction(id).wait_for_receipt()?;
+
+        if receipt.status != Status::Success {
+            Err(ErrorKind::ReceiptStatus(receipt.status))?;
+        }
+
+        receipt.account_id()
+    }
+
     // Update an existing account
     #[inline]
     pub fn update_account(&self, id: AccountId) -> Transaction<TransactionCryptoUpdate> {
         TransactionCryptoUpdate::new(self, id)
     }
 
+    /// Attach a claim to its account. The transaction must be signed by the keys of the
+    /// account as well as every key of the claim.
+    #[inline]
+    pub fn add_claim(&self, claim: Claim) -> Transaction<TransactionCryptoAddClaim> {
+        TransactionCryptoAddClaim::from_claim(self, claim)
+    }
+
     #[inline]
     pub fn account(&self, id: AccountId) -> PartialAccountMessage<'_> {
         PartialAccountMessage(self, id)
     }
 
+    /// Get the balances of several accounts, querying a few of them at a time.
+    ///
+    /// Only the number of queries in flight is bounded; the client has no rate limiter, so
+    /// large snapshots may still be throttled by the node.
+    ///
+    /// Accounts whose balance could not be queried are returned in `errors` instead of
+    /// failing the whole snapshot.
+    pub async fn balances_async(&self, accounts: &[AccountId]) -> Balances {
+        let mut results = stream::iter(accounts.iter().map(|&account| {
+            let mut query = self.account(account).balance();
+            async move { (account, query.get_async().await) }
+        }))
+        .buffer_unordered(MAX_CONCURRENT_BALANCE_QUERIES);
+
+        let mut snapshot = Balances::default();
+
+        while let Some((account, result)) = results.next().await {
+            match result {
+                Ok(balance) => {
+                    snapshot.balances.insert(account, balance);
+                }
+
+                Err(error) => snapshot.errors.push((account, error)),
+            }
+        }
+
+        snapshot
+    }
+
+    pub fn balances(&self, accounts: &[AccountId]) -> Balances {
+        crate::RUNTIME
+            .lock()
+            .block_on(self.balances_async(accounts))
+    }
+
+    /// Wait for the receipts of several transactions, a few of them at a time, as with
+    /// `wait_for_receipt`. The results are in the order of `ids`.
+    pub async fn receipts_async(
+        &self,
+        ids: &[TransactionId],
+    ) -> Vec<Result<TransactionReceipt, Error>> {
+        stream::iter(
+            ids.iter()
+                .map(|&id| self.transaction(id).wait_for_receipt_async()),
+        )
+        .buffered(MAX_CONCURRENT_RECEIPT_QUERIES)
+        .collect()
+        .await
+    }
+
+    pub fn receipts(&self, ids: &[TransactionId]) -> Vec<Result<TransactionReceipt, Error>> {
+        crate::RUNTIME
+            .lock()
+            .block_on(self.receipts_async(ids))
+    }
+
     /// Start a new smart contract instance.
     #[inline]
     pub fn create_contract(&self) -> Transaction<TransactionContractCreate> {
         TransactionContractCreate::new(self)
     }
 
+    /// Upload `bytecode` and create a contract from it in one go.
+    #[inline]
+    pub fn create_contract_flow(&self, bytecode: impl Into<Vec<u8>>) -> ContractCreateFlow {
+        ContractCreateFlow::new(self, bytecode.into())
+    }
+
     #[inline]
     pub fn call_contract(&self, id: ContractId) -> Transaction<TransactionContractCall> {
         TransactionContractCall::new(self, id)
@@ -206,12 +690,146 @@ impl Client {
         TransactionFileCreate::new(self)
     }
 
-    /// Append to an existing file.
+    /// Append to an existing file. The contents must fit in one transaction; see
+    /// `PartialFileMessage::append_chunked` for larger contents.
     #[inline]
     pub fn append_file(&self, id: FileId, contents: Vec<u8>) -> Transaction<TransactionFileAppend> {
         TransactionFileAppend::new(self, id, contents)
     }
 
+    /// Execute a transaction whose body was built from the protobufs, e.g. a kind of
+    /// transaction added to the network after this version of the crate.
+    #[cfg(feature = "proto")]
+    pub fn custom_transaction(
+        &self,
+        data: TransactionBody_oneof_data,
+    ) -> Transaction<TransactionCustom> {
+        TransactionCustom::new(self, data)
+    }
+
+    /// Create a file owned by the operator with `contents` of any size, expiring after
+    /// `lifetime`.
+    ///
+    /// The file is created with as much of the contents as fits in one transaction, and the
+    /// rest is appended in chunks once the receipt of the create gives the file ID; see
+    /// `PartialFileMessage::append_chunked`.
+    ///
+    /// Returns the ID of the file, with the ID of the create followed by those of the appends.
+    /// Use `PartialFileMessage::verify_contents` to check the file once it is uploaded.
+    pub fn create_file_with_contents(
+        &self,
+        contents: impl AsRef<[u8]>,
+        lifetime: Duration,
+    ) -> Result<(FileId, Vec<TransactionId>), Error> {
+        crate::RUNTIME
+            .lock()
+            .block_on(self.create_file_with_contents_async(contents, lifetime))
+    }
+
+    pub fn create_file_with_contents_async(
+        &self,
+        contents: impl AsRef<[u8]>,
+        lifetime: Duration,
+    ) -> impl Future<Output = Result<(FileId, Vec<TransactionId>), Error>> {
+        let client = self.clone();
+        let contents = contents.as_ref();
+        let split = contents.len().min(FILE_APPEND_CHUNK_SIZE);
+        let (first, rest) = (contents[..split].to_vec(), contents[split..].to_vec());
+        let secret = self.operator_secret.as_ref().map(|secret| secret());
+        let mut file = TransactionFileCreate::new(self);
+
+        async move {
+            let secret = secret.ok_or(ErrorKind::MissingField("operator"))??;
+
+            // the operator signs as the payer, and again for the key of the file
+            let id = file
+                .key(secret.public())
+                .contents(first)
+                .expires_in(lifetime)
+                .sign(&secret)
+                .execute_async()
+                .await?;
+
+            let receipt = client.transaction(id).wait_for_receipt_async().await?;
+
+            if receipt.status != Status::Success {
+                Err(ErrorKind::ReceiptStatus(receipt.status))?;
+            }
+
+            let file_id = receipt.file_id()?;
+            let mut ids = vec![id];
+
+            ids.extend(
+                client
+                    .file(file_id)
+                    .append_chunked_async(rest, &[secret])
+                    .await?,
+            );
+
+            Ok((file_id, ids))
+        }
+    }
+
+    /// Store the SHA-384 hash of `bytes` in a new file owned by the operator, so that it can
+    /// later be shown that the payload existed at the consensus time of that file.
+    ///
+    /// The file expires after 30 days unless its expiration is extended.
+    pub fn notarize(&self, bytes: impl AsRef<[u8]>) -> Result<Notarization, Error> {
+        crate::RUNTIME
+            .lock()
+            .block_on(self.notarize_async(bytes))
+    }
+
+    pub fn notarize_async(
+        &self,
+        bytes: impl AsRef<[u8]>,
+    ) -> impl Future<Output = Result<Notarization, Error>> {
+        let client = self.clone();
+        let hash = notarization::hash(bytes.as_ref());
+        let secret = self.operator_secret.as_ref().map(|secret| secret());
+        let mut file = TransactionFileCreate::new(self);
+
+        async move {
+            let secret = secret.ok_or(ErrorKind::MissingField("operator"))??;
+
+            // the operator signs as the payer, and again for the key of the file
+            let id = file
+                .key(secret.public())
+                .contents(hash.clone())
+                .expires_in(NOTARIZATION_LIFETIME)
+                .generate_record(true)
+                .sign(&secret)
+                .execute_async()
+                .await?;
+
+            let receipt = client.transaction(id).wait_for_receipt_async().await?;
+
+            if receipt.status != Status::Success {
+                Err(ErrorKind::ReceiptStatus(receipt.status))?;
+            }
+
+            let record = client.transaction(id).record().get_async().await?;
+
+            Ok(Notarization {
+                file_id: receipt.file_id()?,
+                hash,
+                consensus_time: record
+                    .consensus_timestamp
+                    .ok_or(ErrorKind::MissingField("consensus_timestamp"))?,
+            })
+        }
+    }
+
+    /// Get the nodes of the network from its address book file, e.g. to build a client
+    /// with every node.
+    pub fn address_book(&self) -> Result<Vec<NodeAddress>, Error> {
+        let contents = self.file(network::ADDRESS_BOOK_FILE).contents().get()?;
+
+        NodeAddress::from_address_book(&contents)
+    }
+
+    /// Transactions and queries on an existing file, e.g.
+    /// `client.file(id).append(contents)` or `client.file(id).contents()`.
     #[inline]
     pub fn file(&self, id: FileId) -> PartialFileMessage<'_> {
         PartialFileMessage(self, id)
@@ -221,6 +839,54 @@ impl Client {
     pub fn transaction(&self, id: TransactionId) -> PartialTransactionMessage {
         PartialTransactionMessage(self, id)
     }
+
+    /// Prepare a query built without a client to run through this client, e.g. to
+    /// attach a payment first.
+    #[inline]
+    pub fn query<T>(&self, body: QueryBody<T>) -> Query<T>
+    where
+        T: QueryResponse + Send + Sync + 'static,
+    {
+        body.bind(self)
+    }
+
+    /// Run a query built without a client.
+    pub fn execute_query<T>(&self, body: QueryBody<T>) -> Result<T::Response, Error>
+    where
+        T: QueryResponse + Send + Sync + 'static,
+    {
+        crate::RUNTIME
+            .lock()
+            .block_on(self.execute_query_async(body))
+    }
+
+    pub fn execute_query_async<T>(
+        &self,
+        body: QueryBody<T>,
+    ) -> impl Future<Output = Result<T::Response, Error>>
+    where
+        T: QueryResponse + Send + Sync + 'static,
+    {
+        let mut query = self.query(body);
+
+        async move { query.get_async().await }
+    }
+
+    /// Transactions that require the Hedera admin multisig.
+    #[inline]
+    pub fn admin(&self) -> PartialAdminMessage<'_> {
+        PartialAdminMessage(self)
+    }
+}
+
+/// The balances of several accounts, from `Client::balances`.
+#[derive(Debug, Default)]
+pub struct Balances {
+    /// The balance in tinybar of each account that could be queried.
+    pub balances: HashMap<AccountId, u64>,
+
+    /// The accounts that could not be queried, and why.
+    pub errors: Vec<(AccountId, Error)>,
 }
 
 pub struct PartialAccountMessage<'a>(&'a Client, AccountId);
@@ -238,6 +904,72 @@ impl<'a> PartialAccountMessage<'a> {
         QueryCryptoGetInfo::new(self.0, self.1)
     }
 
+    /// Get the information about the account now and then every `interval`, with what
+    /// changed in its balance, key and expiration since the time before, e.g. to monitor
+    /// an account held in custody.
+    ///
+    /// The stream does not end; a query that fails yields its error and the next one is
+    /// made after `interval` as usual. Each query is paid for like any other.
+    ///
+    /// The wait between queries is a timer of the client's clock, so it does not hold up
+    /// the other requests running on the same thread.
+    pub fn watch_info(
+        self,
+        interval: Duration,
+    ) -> impl Stream<Item = Result<AccountSnapshot, Error>> {
+        account_watch::watch(self.0, self.1, interval)
+    }
+
+    /// Get the records of transfers into and out of the account from the last 25 hours.
+    #[inline]
+    pub fn records(self) -> Query<QueryCryptoGetAccountRecords> {
+        QueryCryptoGetAccountRecords::new(self.0, self.1)
+    }
+
+    /// Get the receipt of every transaction in the records of the account that reached
+    /// consensus between `since` (inclusive) and `until` (exclusive), in consensus order.
+    ///
+    /// Receipts are taken from the records once the network no longer keeps them.
+    pub fn receipts(
+        self,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Result<Vec<(TransactionId, TransactionReceipt)>, Error> {
+        crate::RUNTIME
+            .lock()
+            .block_on(self.receipts_async(since, until))
+    }
+
+    pub fn receipts_async(
+        self,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> impl Future<Output = Result<Vec<(TransactionId, TransactionReceipt)>, Error>> {
+        let client = self.0.clone();
+        let mut records = self.records();
+        records.since(since).until(until);
+
+        async move {
+            let mut receipts = Vec::new();
+
+            for record in records.get_async().await? {
+                let id = record.transaction_id;
+                let receipt = match QueryTransactionGetReceipt::new(&client, id).get_async().await {
+                    Ok(receipt) => receipt,
+
+                    Err(error) => match error.downcast_ref() {
+                        Some(ErrorKind::ReceiptExpired(_)) => record.receipt,
+                        _ => return Err(error),
+                    },
+                };
+
+                receipts.push((id, receipt));
+            }
+
+            Ok(receipts)
+        }
+    }
+
     /// Change properties for the given account. Any missing field is ignored (left unchanged).
     /// This transaction must be signed by the existing key for this account.
     #[inline]
@@ -245,6 +977,17 @@ impl<'a> PartialAccountMessage<'a> {
         TransactionCryptoUpdate::new(self.0, self.1)
     }
 
+    /// Proxy stake the account to `proxy`: a node account, or another account that is
+    /// itself staked to a node. This transaction must be signed by the key of the account.
+    ///
+    /// The account stays staked to `proxy` until it is changed again; see
+    /// `AccountInfo::staking` for where an account is staked.
+    pub fn stake_to(self, proxy: AccountId) -> Transaction<TransactionCryptoUpdate> {
+        let mut transaction = TransactionCryptoUpdate::new(self.0, self.1);
+        transaction.proxy_account(proxy);
+        transaction
+    }
+
     /// Mark an account as deleted, moving all its current hbars to another account.
     /// It will remain in the ledger, marked as deleted, until it expires.
     #[inline]
@@ -261,6 +1004,13 @@ impl<'a> PartialAccountMessage<'a> {
 pub struct PartialAccountClaimMessage<'a>(PartialAccountMessage<'a>, Vec<u8>);
 
 impl<'a> PartialAccountClaimMessage<'a> {
+    /// Attach the claim hash to the account. Add the keys of the claim with `key`; the
+    /// transaction must be signed by the keys of the account and every key of the claim.
+    #[inline]
+    pub fn add(self) -> Transaction<TransactionCryptoAddClaim> {
+        TransactionCryptoAddClaim::new((self.0).0, (self.0).1, self.1)
+    }
+
     /// Delete a claim hash that was attached to the given account.
     /// This transaction is valid if signed by all the keys used for transfers out of the account.
     #[inline]
@@ -277,16 +1027,81 @@ impl<'a> PartialAccountClaimMessage<'a> {
 pub struct PartialFileMessage<'a>(&'a Client, FileId);
 
 impl<'a> PartialFileMessage<'a> {
+    /// Append `contents` to the end of the file, in a single transaction; use `append_chunked`
+    /// for contents larger than a transaction can hold.
     #[inline]
     pub fn append(self, contents: Vec<u8>) -> Transaction<TransactionFileAppend> {
         TransactionFileAppend::new(self.0, self.1, contents)
     }
 
+    /// Append `contents` of any size to the file, split into as many appends as needed to
+    /// keep each transaction under the size limit of the network.
+    ///
+    /// The appends are sent one at a time, each once the receipt of the one before shows it
+    /// succeeded, so that the chunks are added in order. They must be signed by every key of
+    /// the file; `secrets` are the secrets of those keys, apart from the operator's.
+    ///
+    /// Returns the ID of each append. If one fails, the chunks before it stay appended.
+    pub fn append_chunked(
+        self,
+        contents: impl AsRef<[u8]>,
+        secrets: &[SecretKey],
+    ) -> Result<Vec<TransactionId>, Error> {
+        crate::RUNTIME
+            .lock()
+            .block_on(self.append_chunked_async(contents, secrets))
+    }
+
+    pub fn append_chunked_async(
+        self,
+        contents: impl AsRef<[u8]>,
+        secrets: &[SecretKey],
+    ) -> impl Future<Output = Result<Vec<TransactionId>, Error>> {
+        let client = self.0.clone();
+        let file = self.1;
+        let chunks: Vec<Vec<u8>> = contents
+            .as_ref()
+            .chunks(FILE_APPEND_CHUNK_SIZE)
+            .map(<[u8]>::to_vec)
+            .collect();
+        let secrets = secrets.to_vec();
+
+        async move {
+            let mut ids = Vec::with_capacity(chunks.len());
+
+            for chunk in chunks {
+                let mut append = TransactionFileAppend::new(&client, file, chunk);
+
+                for secret in &secrets {
+                    append.sign(secret);
+                }
+
+                let id = append.execute_async().await?;
+                let receipt = client.transaction(id).wait_for_receipt_async().await?;
+
+                if receipt.status != Status::Success {
+                    Err(ErrorKind::ReceiptStatus(receipt.status))?;
+                }
+
+                ids.push(id);
+            }
+
+            Ok(ids)
+        }
+    }
+
+    /// Delete the file. The transaction must be signed by every key of the file.
     #[inline]
     pub fn delete(self) -> Transaction<TransactionFileDelete> {
         TransactionFileDelete::new(self.0, self.1)
     }
 
+    /// Change the expiration, keys or contents of a file. Anything not set is left unchanged.
+    #[inline]
+    pub fn update(self) -> Transaction<TransactionFileUpdate> {
+        TransactionFileUpdate::new(self.0, self.1)
+    }
+
     #[inline]
     pub fn info(self) -> Query<QueryFileGetInfo> {
         QueryFileGetInfo::new(self.0, self.1)
@@ -296,20 +1111,228 @@ impl<'a> PartialFileMessage<'a> {
     pub fn contents(self) -> Query<QueryFileGetContents> {
         QueryFileGetContents::new(self.0, self.1)
     }
+
+    /// Read the contents of the file back and check that they are exactly `expected`, e.g.
+    /// after `Client::create_file_with_contents` or `append_chunked`, before deleting the local
+    /// copy. Fails with `ContentMismatch` if they differ.
+    ///
+    /// The contents are read with a paid query, so the check is left to the caller.
+    pub fn verify_contents(self, expected: impl AsRef<[u8]>) -> Result<(), Error> {
+        crate::RUNTIME
+            .lock()
+            .block_on(self.verify_contents_async(expected))
+    }
+
+    pub fn verify_contents_async(
+        self,
+        expected: impl AsRef<[u8]>,
+    ) -> impl Future<Output = Result<(), Error>> {
+        let file = self.1;
+        let mut contents = QueryFileGetContents::new(self.0, self.1);
+        let expected = expected.as_ref().to_vec();
+
+        async move {
+            let contents = contents.get_async().await?;
+
+            if contents != expected {
+                Err(ErrorKind::ContentMismatch {
+                    file,
+                    expected: expected.len(),
+                    actual: contents.len(),
+                })?;
+            }
+
+            Ok(())
+        }
+    }
+
+    /// Get both the information and the contents of a file.
+    ///
+    /// This is two queries, an info query and then a contents query, and the operator pays
+    /// for each of them; the contents query costs more the larger the file. Use
+    /// `get_with_max_cost` to cap what the pair may cost.
+    pub fn get(self) -> Result<File, Error> {
+        crate::RUNTIME.lock().block_on(self.get_async())
+    }
+
+    pub fn get_async(self) -> impl Future<Output = Result<File, Error>> {
+        self.get_file_async(None)
+    }
+
+    /// Get both the information and the contents of a file, as `get`, unless the two
+    /// queries together cost more than `max_cost` tinybar.
+    ///
+    /// The costs of both queries are asked for first, so neither is paid for if the pair
+    /// costs too much.
+    pub fn get_with_max_cost(self, max_cost: u64) -> Result<File, Error> {
+        crate::RUNTIME
+            .lock()
+            .block_on(self.get_with_max_cost_async(max_cost))
+    }
+
+    pub fn get_with_max_cost_async(
+        self,
+        max_cost: u64,
+    ) -> impl Future<Output = Result<File, Error>> {
+        self.get_file_async(Some(max_cost))
+    }
+
+    fn get_file_async(self, max_cost: Option<u64>) -> impl Future<Output = Result<File, Error>> {
+        let mut info = QueryFileGetInfo::new(self.0, self.1);
+        let mut contents = QueryFileGetContents::new(self.0, self.1);
+
+        async move {
+            if let Some(max) = max_cost {
+                let costs = (info.cost_async().await?, contents.cost_async().await?);
+                check_total_cost(costs, max)?;
+            }
+
+            let info = info.get_async().await?;
+            let contents = contents.get_async().await?;
+
+            Ok(File { info, contents })
+        }
+    }
+
+    /// Push the expiration of a file back by `extension`, keeping it alive without
+    /// uploading its contents again.
+    ///
+    /// The current expiration is read from the file info. The update must be signed by every
+    /// key of the file; `secrets` are the secrets of those keys, apart from the operator's.
+    pub fn extend_expiration(
+        self,
+        extension: Duration,
+        secrets: &[SecretKey],
+    ) -> Result<TransactionId, Error> {
+        crate::RUNTIME
+            .lock()
+            .block_on(self.extend_expiration_async(extension, secrets))
+    }
+
+    pub fn extend_expiration_async(
+        self,
+        extension: Duration,
+        secrets: &[SecretKey],
+    ) -> impl Future<Output = Result<TransactionId, Error>> {
+        let mut info = QueryFileGetInfo::new(self.0, self.1);
+        let update = TransactionFileUpdate::new(self.0, self.1);
+        let operator = self.0.operator_secret.as_ref().map(|secret| secret());
+        let mut secrets = secrets.to_vec();
+
+        async move {
+            let info = info.get_async().await?;
+
+            // the operator signs as the payer, but a file it keys needs its signature again
+            if let Some(operator) = operator {
+                secrets.push(operator?);
+            }
+
+            let mut update = update;
+            update.expires_at(info.expiration_time + ChronoDuration::from_std(extension)?);
+
+            let mut update = update.freeze();
+
+            for key in &info.keys {
+                let secret = secrets
+                    .iter()
+                    .find(|secret| secret.public() == *key)
+                    .ok_or_else(|| ErrorKind::MissingSigner(key.clone()))?;
+
+                update.sign(secret);
+            }
+
+            update.execute_async().await
+        }
+    }
 }
 
 pub struct PartialContractMessage<'a>(&'a Client, ContractId);
 
 impl<'a> PartialContractMessage<'a> {
+    /// Call a function of the contract in a transaction, so that it can change the state
+    /// of the contract.
     #[inline]
     pub fn call(self) -> Transaction<TransactionContractCall> {
         TransactionContractCall::new(self.0, self.1)
     }
 
+    /// Change the admin key, expiration, bytecode file or proxy account of the contract.
+    /// The transaction must be signed by the admin key of the contract.
     #[inline]
     pub fn update(self) -> Transaction<TransactionContractUpdate> {
         TransactionContractUpdate::new(self.0, self.1)
     }
+
+    /// Delete the contract, moving its hbars to the account set with `obtainer_account`.
+    /// The transaction must be signed by the admin key of the contract.
+    #[inline]
+    pub fn delete(self) -> Transaction<TransactionContractDelete> {
+        TransactionContractDelete::new(self.0, self.1)
+    }
+
+    /// Run a function of the contract on one node without a transaction; it cannot change
+    /// the state of the contract.
+    #[inline]
+    pub fn call_local(
+        self,
+        gas: i64,
+        function_parameters: Vec<u8>,
+        max_result_size: i64,
+    ) -> Query<QueryContractCallLocal> {
+        QueryContractCallLocal::new(self.0, self.1, gas, function_parameters, max_result_size)
+    }
+
+    /// Get information about a smart contract instance.
+    #[inline]
+    pub fn info(self) -> Query<QueryContractGetInfo> {
+        QueryContractGetInfo::new(self.0, self.1)
+    }
+
+    /// Get the bytecode of a smart contract instance.
+    ///
+    /// Bytecode can be large; use `max_cost` to refuse oversized responses from the quoted
+    /// cost and `write_to` to stream the bytecode out instead of keeping a copy in memory.
+    #[inline]
+    pub fn bytecode(self) -> Query<QueryContractGetBytecode> {
+        QueryContractGetBytecode::new(self.0, self.1)
+    }
+}
+
+pub struct PartialAdminMessage<'a>(&'a Client);
+
+impl<'a> PartialAdminMessage<'a> {
+    /// Delete a file. It is kept until the expiration time set on the transaction
+    /// and can be recovered until then.
+    #[inline]
+    pub fn delete_file(self, id: FileId) -> Transaction<TransactionAdminDelete> {
+        TransactionAdminDelete::file(self.0, id)
+    }
+
+    /// Delete a contract. It is kept until the expiration time set on the transaction
+    /// and can be recovered until then. The account of the contract is not affected.
+    #[inline]
+    pub fn delete_contract(self, id: ContractId) -> Transaction<TransactionAdminDelete> {
+        TransactionAdminDelete::contract(self.0, id)
+    }
+
+    /// Recover a file deleted by `delete_file` before it expires.
+    #[inline]
+    pub fn recover_file(self, id: FileId) -> Transaction<TransactionAdminRecover> {
+        TransactionAdminRecover::file(self.0, id)
+    }
+
+    /// Recover a contract deleted by `delete_contract` before it expires.
+    #[inline]
+    pub fn recover_contract(self, id: ContractId) -> Transaction<TransactionAdminRecover> {
+        TransactionAdminRecover::contract(self.0, id)
+    }
+
+    /// Freeze the network for maintenance, between the times of day set with `start`
+    /// and `end`.
+    #[inline]
+    pub fn freeze(self) -> Transaction<TransactionFreeze> {
+        TransactionFreeze::new(self.0)
+    }
 }
 
 pub struct PartialTransactionMessage<'a>(&'a Client, TransactionId);
@@ -328,8 +1351,126 @@ impl<'a> PartialTransactionMessage<'a> {
     ///
     /// If the transaction requested a record, then the record lasts for one hour, and a state
     /// proof is available for it.
+    ///
+    /// Unlike the receipt, the record costs a fee; the query is paid for through the same
+    /// automatic query payment flow as any other query made from this client.
     #[inline]
     pub fn record(self) -> Query<QueryTransactionGetRecord> {
         QueryTransactionGetRecord::new(self.0, self.1)
     }
+
+    /// Wait for a transaction to reach consensus and return its receipt.
+    ///
+    /// The receipt is polled until it has a status, or until the transaction can
+    /// no longer reach consensus.
+    pub fn wait_for_receipt(self) -> Result<TransactionReceipt, Error> {
+        crate::RUNTIME
+            .lock()
+            .block_on(self.wait_for_receipt_async())
+    }
+
+    pub fn wait_for_receipt_async(
+        self,
+    ) -> impl Future<Output = Result<TransactionReceipt, Error>> {
+        let client = self.0.clone();
+        let id = self.1;
+
+        // the transaction must reach consensus within its valid duration; its receipt may take
+        // a little longer to be available
+        let deadline = id.transaction_valid_start
+            + ChronoDuration::from_std(transaction::VALID_DURATION + RECEIPT_DELAY).unwrap();
+
+        async move {
+            loop {
+                match QueryTransactionGetReceipt::new(&client, id).get_async().await {
+                    Ok(receipt) => {
+                        if receipt.status != Status::Unknown {
+                            return Ok(receipt);
+                        }
+                    }
+
+                    Err(error) => match error.downcast_ref() {
+                        Some(ErrorKind::PreCheck(Status::ReceiptNotFound)) => {}
+                        _ => return Err(error),
+                    },
+                }
+
+                if client.clock.now() > deadline {
+                    Err(ErrorKind::PreCheck(Status::ReceiptNotFound))?;
+                }
+
+                client.clock.sleep(Duration::from_secs(1)).await;
+            }
+        }
+    }
+
+    /// Get the receipt of a transaction, falling back to the receipt inside its record
+    /// if the receipt is no longer available.
+    ///
+    /// Records are kept for longer than receipts but are not free to query, and only exist
+    /// if the transaction asked for a record to be generated.
+    pub fn receipt_or_record(self) -> Result<TransactionReceipt, Error> {
+        crate::RUNTIME
+            .lock()
+            .block_on(self.receipt_or_record_async())
+    }
+
+    pub fn receipt_or_record_async(
+        self,
+    ) -> impl Future<Output = Result<TransactionReceipt, Error>> {
+        let mut receipt = QueryTransactionGetReceipt::new(self.0, self.1);
+        let mut record = QueryTransactionGetRecord::new(self.0, self.1);
+
+        async move {
+            match receipt.get_async().await {
+                Err(error) => match error.downcast_ref() {
+                    Some(ErrorKind::ReceiptExpired(_)) => Ok(record.get_async().await?.receipt),
+                    _ => Err(error),
+                },
+
+                receipt => receipt,
+            }
+        }
+    }
+}
+
+// Fail if a pair of queries costs more than `max` together
+fn check_total_cost((first, second): (u64, u64), max: u64) -> Result<(), Error> {
+    match first.checked_add(second) {
+        Some(cost) if cost <= max => Ok(()),
+        cost => Err(ErrorKind::QueryCostExceeded {
+            cost: cost.unwrap_or(u64::max_value()),
+            max,
+        })?,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check_total_cost, Client};
+    use crate::{AccountId, SecretKey};
+    use failure::Error;
+
+    #[test]
+    fn test_check_total_cost() {
+        assert!(check_total_cost((100, 200), 300).is_ok());
+        assert!(check_total_cost((100, 201), 300).is_err());
+        assert!(check_total_cost((u64::max_value(), 1), u64::max_value()).is_err());
+    }
+
+    #[test]
+    fn test_send_rejects_non_positive_amounts() -> Result<(), Error> {
+        let client = Client::builder()
+            .node("0.0.3@127.0.0.1:50211".parse()?)
+            .operator(AccountId::new(0, 0, 1001), || SecretKey::generate("").0)
+            .build()?;
+
+        let (from, to) = (AccountId::new(0, 0, 1001), AccountId::new(0, 0, 1002));
+
+        assert!(client.send(from, to, 0).is_err());
+        assert!(client.send(from, to, -100).is_err());
+        assert!(client.send(from, to, i64::min_value()).is_err());
+
+        Ok(())
+    }
 }
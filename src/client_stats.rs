@@ -0,0 +1,40 @@
+use crate::Status;
+use std::collections::HashMap;
+
+/// Counts of what the network answered to the requests of a client (and every clone of
+/// it) since it was built, from `Client::stats`.
+#[derive(Debug, Clone, Default)]
+pub struct ClientStats {
+    /// How many responses of each pre-check status transactions and queries got, retried
+    /// `Busy` responses included.
+    pub prechecks: HashMap<Status, u64>,
+
+    /// How many receipts of each status were received.
+    pub receipts: HashMap<Status, u64>,
+
+    /// How many requests were sent again after a `Busy` response.
+    pub retries: u64,
+
+    /// The tinybars committed to fees: the maximum fee of each transaction that passed
+    /// pre-check, and the payment of each query that was answered. The fees charged by the
+    /// network can be lower; the records of the transactions have the actual fees.
+    pub fees: u64,
+}
+
+impl ClientStats {
+    pub(crate) fn record_precheck(&mut self, status: Status) {
+        *self.prechecks.entry(status).or_insert(0) += 1;
+    }
+
+    pub(crate) fn record_receipt(&mut self, status: Status) {
+        *self.receipts.entry(status).or_insert(0) += 1;
+    }
+
+    pub(crate) fn record_retry(&mut self) {
+        self.retries += 1;
+    }
+
+    pub(crate) fn record_fee(&mut self, fee: u64) {
+        self.fees = self.fees.saturating_add(fee);
+    }
+}
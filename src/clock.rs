@@ -0,0 +1,39 @@
+use chrono::{DateTime, Utc};
+use futures::future::BoxFuture;
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// The source of the current time and of the waits between retries.
+///
+/// Transaction IDs, valid window checks, expiration times set with `expires_in` and the
+/// delays before retrying or polling for a receipt all go through the clock of the client,
+/// so that tests can replace it (with `ClientBuilder::clock`) and control time.
+pub trait Clock: Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+
+    /// Wait for `duration` without blocking the thread; a test clock may advance its time
+    /// instead and complete right away.
+    fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()>;
+}
+
+/// The clock of the system, used unless another one is set on the client.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()> {
+        Box::pin(tokio::timer::delay(Instant::now() + duration))
+    }
+}
+
+pub(crate) type SharedClock = Arc<dyn Clock>;
+
+pub(crate) fn system() -> SharedClock {
+    Arc::new(SystemClock)
+}
@@ -0,0 +1,138 @@
+use crate::{crypto::PublicKey, AccountId, Client, ContractId, ErrorKind, Status};
+use failure::Error;
+use futures::Future;
+use std::time::Duration;
+
+// How long the bytecode file is kept if it is not deleted once the contract is created
+const BYTECODE_FILE_LIFETIME: Duration = Duration::from_secs(2_592_000);
+
+/// Create a contract from its bytecode: upload the bytecode to a file (in chunks if it is
+/// large), create the contract from that file and, unless `keep_bytecode_file` is set,
+/// delete the file again. Start one with `Client::create_contract_flow`.
+///
+/// The bytecode file is owned by the operator. Each step waits for the receipt of the one
+/// before, and the flow fails with the status of the first step that did not succeed.
+pub struct ContractCreateFlow {
+    client: Client,
+    bytecode: Vec<u8>,
+    gas: i64,
+    initial_balance: i64,
+    constructor_parameters: Option<Vec<u8>>,
+    admin_key: Option<PublicKey>,
+    proxy_account: Option<AccountId>,
+    auto_renew_period: Option<Duration>,
+    keep_bytecode_file: bool,
+}
+
+impl ContractCreateFlow {
+    pub(crate) fn new(client: &Client, bytecode: Vec<u8>) -> Self {
+        Self {
+            client: client.clone(),
+            bytecode,
+            gas: 0,
+            initial_balance: 0,
+            constructor_parameters: None,
+            admin_key: None,
+            proxy_account: None,
+            auto_renew_period: None,
+            keep_bytecode_file: false,
+        }
+    }
+
+    /// The gas to run the constructor with.
+    pub fn gas(&mut self, gas: i64) -> &mut Self {
+        self.gas = gas;
+        self
+    }
+
+    /// The tinybars to move from the payer into the account of the contract.
+    pub fn initial_balance(&mut self, balance: i64) -> &mut Self {
+        self.initial_balance = balance;
+        self
+    }
+
+    /// The ABI-encoded parameters of the constructor, e.g. from `CallParams::assemble`.
+    pub fn constructor_parameters(&mut self, params: Vec<u8>) -> &mut Self {
+        self.constructor_parameters = Some(params);
+        self
+    }
+
+    pub fn admin_key(&mut self, key: PublicKey) -> &mut Self {
+        self.admin_key = Some(key);
+        self
+    }
+
+    pub fn proxy_account(&mut self, account: AccountId) -> &mut Self {
+        self.proxy_account = Some(account);
+        self
+    }
+
+    pub fn auto_renew_period(&mut self, period: Duration) -> &mut Self {
+        self.auto_renew_period = Some(period);
+        self
+    }
+
+    /// Leave the bytecode file on the network once the contract is created, e.g. to create
+    /// more instances of the contract from it.
+    pub fn keep_bytecode_file(&mut self, keep: bool) -> &mut Self {
+        self.keep_bytecode_file = keep;
+        self
+    }
+
+    pub fn execute(&mut self) -> Result<ContractId, Error> {
+        crate::RUNTIME
+            .lock()
+            .block_on(self.execute_async())
+    }
+
+    pub fn execute_async(&mut self) -> impl Future<Output = Result<ContractId, Error>> {
+        let client = self.client.clone();
+        let upload =
+            client.create_file_with_contents_async(self.bytecode.clone(), BYTECODE_FILE_LIFETIME);
+        let mut create = client.create_contract();
+
+        create.gas(self.gas).initial_balance(self.initial_balance);
+
+        if let Some(params) = &self.constructor_parameters {
+            create.constructor_parameters(params.clone());
+        }
+
+        if let Some(key) = &self.admin_key {
+            create.admin_key(key.clone());
+        }
+
+        if let Some(account) = self.proxy_account {
+            create.proxy_account(account);
+        }
+
+        if let Some(period) = self.auto_renew_period {
+            create.auto_renew_period(period);
+        }
+
+        let keep_bytecode_file = self.keep_bytecode_file;
+
+        async move {
+            let (file, _) = upload.await?;
+
+            let id = create.file(file).execute_async().await?;
+            let receipt = client.transaction(id).wait_for_receipt_async().await?;
+
+            if receipt.status != Status::Success {
+                Err(ErrorKind::ReceiptStatus(receipt.status))?;
+            }
+
+            let contract = receipt.contract_id()?;
+
+            if !keep_bytecode_file {
+                let id = client.file(file).delete().execute_async().await?;
+                let receipt = client.transaction(id).wait_for_receipt_async().await?;
+
+                if receipt.status != Status::Success {
+                    Err(ErrorKind::ReceiptStatus(receipt.status))?;
+                }
+            }
+
+            Ok(contract)
+        }
+    }
+}
@@ -1,3 +1,5 @@
+//! Ed25519 keys and signatures. The same types are re-exported at the root of the crate.
+
 use crate::proto::{self, ToProto};
 use bip39::{Language, Mnemonic, MnemonicType, Seed};
 use ed25519_dalek;
@@ -6,6 +8,7 @@ use failure_derive::Fail;
 use hex;
 use num::BigUint;
 use once_cell::{sync::Lazy};
+use protobuf::{parse_from_bytes, Message};
 use rand_core::SeedableRng;
 use rand_chacha::ChaChaRng;
 use simple_asn1::{
@@ -14,8 +17,10 @@ use simple_asn1::{
 };
 use std::{
     fmt::{self, Debug, Display},
+    hash::{Hash, Hasher},
     str::FromStr,
 };
+use subtle::ConstantTimeEq;
 use try_from::{TryFrom, TryInto};
 
 // Types used for (de-)serializing public and secret keys from ASN.1 byte
@@ -244,7 +249,7 @@ impl FromASN1 for PrivateKeyInfo {
 }
 
 /// An ed25519 public key.
-#[derive(PartialEq, Clone)]
+#[derive(Clone)]
 #[repr(C)]
 pub struct PublicKey(ed25519_dalek::PublicKey);
 
@@ -297,6 +302,17 @@ impl PublicKey {
         .unwrap()
     }
 
+    /// Encode the `PublicKey` as a protobuf `Key`, the form keys take in files, in claims
+    /// and in the information returned by queries.
+    pub fn to_proto_key_bytes(&self) -> Result<Vec<u8>, Error> {
+        Ok(self.to_proto()?.write_to_bytes()?)
+    }
+
+    /// Decode a `PublicKey` from a protobuf `Key` holding a single ed25519 key.
+    pub fn from_proto_key_bytes(bytes: impl AsRef<[u8]>) -> Result<Self, Error> {
+        parse_from_bytes::<proto::BasicTypes::Key>(bytes.as_ref())?.try_into()
+    }
+
     /// Verify a signature on a message with this `PublicKey`.
     pub fn verify(&self, message: impl AsRef<[u8]>, signature: &Signature) -> Result<bool, Error> {
         match self.0.verify(message.as_ref(), &signature.0) {
@@ -323,6 +339,21 @@ impl FromStr for PublicKey {
     }
 }
 
+/// Compares the raw bytes of the keys.
+impl PartialEq for PublicKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl Eq for PublicKey {}
+
+impl Hash for PublicKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_bytes().hash(state);
+    }
+}
+
 impl Debug for PublicKey {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "\"{}\"", self)
@@ -469,6 +500,16 @@ impl Clone for SecretKey {
     }
 }
 
+/// Compares the raw bytes of the keys in constant time, so that the comparison does not
+/// leak how many leading bytes match.
+impl PartialEq for SecretKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_bytes()[..].ct_eq(&other.as_bytes()[..]).into()
+    }
+}
+
+impl Eq for SecretKey {}
+
 /// Construct a `SecretKey` from a hex representation of a raw or ASN.1 encoded
 /// key.
 impl FromStr for SecretKey {
@@ -591,6 +632,7 @@ impl<'a> ToProto<proto::BasicTypes::Signature> for &'a [&'a Signature] {
 mod tests {
     use super::{PublicKey, SecretKey, Signature};
     use failure::Error;
+    use std::collections::HashSet;
 
     const KEY_PUBLIC_ASN1_HEX: &str =
         "302a300506032b6570032100e0c8ec2758a5879ffac226a13c0c516b799e72e35141a0dd828f94d37988a4b7";
@@ -616,6 +658,7 @@ mod tests {
 
         assert_eq!(public_key1, public_key2);
         assert_eq!(secret_key1.0.as_bytes(), secret_key2.0.as_bytes());
+        assert!(secret_key1 == secret_key2);
         assert_eq!(public_key1, secret_key1.public());
         assert_eq!(public_key2, secret_key2.public());
         assert_eq!(secret_key2.public(), secret_key1.public());
@@ -623,6 +666,32 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_dedup_public_keys() -> Result<(), Error> {
+        let keys: HashSet<PublicKey> = vec![
+            KEY_PUBLIC_ASN1_HEX.parse()?,
+            KEY_PUBLIC_HEX.parse()?,
+            SecretKey::generate("").0.public(),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(keys.len(), 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_proto_key_bytes() -> Result<(), Error> {
+        let key: PublicKey = KEY_PUBLIC_ASN1_HEX.parse()?;
+        let bytes = key.to_proto_key_bytes()?;
+
+        assert_eq!(PublicKey::from_proto_key_bytes(&bytes)?, key);
+        assert!(PublicKey::from_proto_key_bytes(&[0xff]).is_err());
+
+        Ok(())
+    }
+
     #[test]
     fn test_verify() -> Result<(), Error> {
         let key: PublicKey = KEY_PUBLIC_ASN1_HEX.parse()?;
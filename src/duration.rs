@@ -1,7 +1,26 @@
-use crate::proto::{self, ToProto};
+use crate::{
+    network::{MAX_AUTO_RENEW_PERIOD, MIN_AUTO_RENEW_PERIOD},
+    proto::{self, ToProto},
+    ErrorKind,
+};
 use failure::Error;
+use std::time::Duration;
 use try_from::{TryFrom, TryInto};
 
+/// Check `period` against the auto-renew periods the network accepts, so that a transaction
+/// with a period out of range fails to build rather than pay for a failed pre-check.
+pub(crate) fn check_auto_renew_period(period: Duration) -> Result<(), Error> {
+    if period < MIN_AUTO_RENEW_PERIOD || period > MAX_AUTO_RENEW_PERIOD {
+        Err(ErrorKind::AutoRenewPeriodOutOfRange {
+            period: period.as_secs(),
+            min: MIN_AUTO_RENEW_PERIOD.as_secs(),
+            max: MAX_AUTO_RENEW_PERIOD.as_secs(),
+        })?;
+    }
+
+    Ok(())
+}
+
 impl ToProto<proto::Duration::Duration> for std::time::Duration {
     fn to_proto(&self) -> Result<proto::Duration::Duration, Error> {
         let mut duration = proto::Duration::Duration::new();
@@ -23,3 +42,18 @@ impl TryFrom<proto::Duration::Duration> for std::time::Duration {
         ))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::check_auto_renew_period;
+    use crate::network::{MAX_AUTO_RENEW_PERIOD, MIN_AUTO_RENEW_PERIOD};
+    use std::time::Duration;
+
+    #[test]
+    fn test_check_auto_renew_period() {
+        assert!(check_auto_renew_period(MIN_AUTO_RENEW_PERIOD).is_ok());
+        assert!(check_auto_renew_period(MAX_AUTO_RENEW_PERIOD).is_ok());
+        assert!(check_auto_renew_period(Duration::from_secs(2_592_000)).is_err());
+        assert!(check_auto_renew_period(MAX_AUTO_RENEW_PERIOD + Duration::from_secs(1)).is_err());
+    }
+}
@@ -3,6 +3,7 @@ use failure::Error;
 use protobuf::RepeatedField;
 use try_from::TryInto;
 
+#[derive(Debug, Clone, PartialEq)]
 pub enum Entity {
     Account(AccountId),
     Claim(Claim),
@@ -1,4 +1,5 @@
-use crate::Status;
+use crate::{AccountId, FileId, PublicKey, Status, TransactionId};
+use failure::Error;
 use failure_derive::Fail;
 
 #[derive(Debug, Fail)]
@@ -11,4 +12,224 @@ pub enum ErrorKind {
 
     #[fail(display = "pre-check failed with status: {:?}", _0)]
     PreCheck(Status),
+
+    /// The transaction reached consensus, but failed there with the status of its receipt.
+    #[fail(display = "transaction failed with status: {:?}", _0)]
+    ReceiptStatus(Status),
+
+    /// Unless `not_sent`, the request may have reached the node, and a transaction may
+    /// still reach consensus; check for its receipt before sending it again.
+    #[fail(display = "transport error: {}", message)]
+    TransportError {
+        code: Option<i32>,
+        message: String,
+        not_sent: bool,
+    },
+
+    #[fail(display = "node {} is not in the network of this client", _0)]
+    UnknownNode(AccountId),
+
+    #[fail(display = "invalid entity ID {}: {}", id, reason)]
+    InvalidEntityId { id: String, reason: &'static str },
+
+    #[fail(display = "no operator is registered under the name {:?}", _0)]
+    UnknownOperator(String),
+
+    #[fail(display = "the operator secret (public key {}) is not the key of {}", key, operator)]
+    OperatorKeyMismatch { operator: AccountId, key: PublicKey },
+
+    #[fail(display = "{} is not in shard {} realm {} of the network", id, shard, realm)]
+    WrongShardRealm { id: String, shard: i64, realm: i64 },
+
+    #[fail(display = "receipt has no {} ID (status {:?})", kind, status)]
+    ReceiptMissingId { kind: &'static str, status: Status },
+
+    #[fail(display = "transaction {} has not reached consensus, so it has no record", _0)]
+    RecordNotGenerated(TransactionId),
+
+    #[fail(display = "receipt for transaction {} is no longer available", _0)]
+    ReceiptExpired(TransactionId),
+
+    #[fail(display = "record does not check out: {}", _0)]
+    InconsistentRecord(&'static str),
+
+    #[fail(display = "valid window of transaction {} passed before it was accepted", _0)]
+    ValidWindowElapsed(TransactionId),
+
+    #[fail(display = "the budget of the workflow ran out of {}", _0)]
+    BudgetExhausted(&'static str),
+
+    #[fail(display = "the query is free and must not be given a payment")]
+    UnexpectedPayment,
+
+    #[fail(display = "query cost of {} tinybar exceeds the maximum of {}", cost, max)]
+    QueryCostExceeded { cost: u64, max: u64 },
+
+    /// The client has strict payments and the query was not given a payment; `cost` is
+    /// what the node asks for the query, not counting the fee of the payment itself.
+    #[fail(display = "the query costs {} tinybar and must be given a payment", cost)]
+    PaymentRequired { cost: u64 },
+
+    #[fail(display = "transaction failed to build ({}); ignored calls to: {}", cause, setters)]
+    IgnoredSetters { setters: String, cause: String },
+
+    #[fail(display = "signature does not match the transaction for key {}", _0)]
+    InvalidSignature(PublicKey),
+
+    #[fail(display = "no secret was given for required signer {}", _0)]
+    MissingSigner(PublicKey),
+
+    #[fail(display = "key {} is not required to sign this transaction", _0)]
+    UnexpectedSigner(PublicKey),
+
+    #[fail(display = "contract call failed: {}", _0)]
+    ContractCall(String),
+
+    #[fail(display = "contract data is {} bytes long; expected at least {}", actual, expected)]
+    ContractDataTooShort { expected: usize, actual: usize },
+
+    #[fail(display = "{} transactions are not supported", _0)]
+    UnsupportedTransactionKind(&'static str),
+
+    #[fail(display = "{} queries are not supported", _0)]
+    UnsupportedQueryKind(&'static str),
+
+    /// The node refused the request with `InsufficientTxFee`. `offered` is the maximum fee
+    /// of the transaction, or the payment of the query; `required` is the cost of the query
+    /// as reported by the node (not counting the fee of the payment itself). Nodes do not
+    /// report what a transaction would need.
+    #[fail(display = "a fee of {} tinybar is insufficient", offered)]
+    InsufficientFee { offered: u64, required: Option<u64> },
+
+    #[fail(display = "fee policy rejected a {} transaction with a fee of {} tinybar", kind, fee)]
+    FeeRejected { kind: &'static str, fee: u64 },
+
+    #[fail(display = "{:02}:{:02} is not a time of day", hour, minute)]
+    InvalidFreezeTime { hour: u8, minute: u8 },
+
+    #[fail(display = "auto-renew period of {}s is not between {}s and {}s", period, min, max)]
+    AutoRenewPeriodOutOfRange { period: u64, min: u64, max: u64 },
+
+    #[fail(display = "invalid transfer list: {}", _0)]
+    InvalidTransferList(String),
+
+    #[fail(display = "record threshold of {} tinybar is negative", _0)]
+    NegativeRecordThreshold(i64),
+
+    #[fail(display = "memo is {} bytes long; the maximum is 100", _0)]
+    MemoTooLong(usize),
+
+    #[fail(
+        display = "file {} does not match the uploaded contents ({} bytes expected, {} found)",
+        file, expected, actual
+    )]
+    ContentMismatch {
+        file: FileId,
+        expected: usize,
+        actual: usize,
+    },
+
+    #[fail(display = "the client has been shut down")]
+    ClientShutdown,
+
+    #[fail(display = "shutdown timed out with {} requests still in flight", pending)]
+    ShutdownTimeout { pending: usize },
+}
+
+/// Whether a transaction that failed with an error may still reach consensus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionState {
+    /// The transaction never reached the network, or the node refused it;
+    /// it is safe to send again, even under a new transaction ID.
+    DefinitelyNotSubmitted,
+
+    /// The transaction may have been submitted. Check for its receipt before sending it again;
+    /// sending it under a new transaction ID could execute it twice.
+    PossiblySubmitted,
+}
+
+impl TransactionState {
+    /// The state of a transaction after `error`. Errors that are not an `ErrorKind`
+    /// are assumed to leave the transaction possibly submitted.
+    pub fn of(error: &Error) -> Self {
+        error
+            .downcast_ref::<ErrorKind>()
+            .map_or(TransactionState::PossiblySubmitted, ErrorKind::transaction_state)
+    }
+}
+
+impl ErrorKind {
+    pub fn transaction_state(&self) -> TransactionState {
+        use TransactionState::*;
+
+        match self {
+            // the network already has a transaction with the same ID, or we could not find out
+            // what happened to it
+            ErrorKind::PreCheck(Status::DuplicateTransaction)
+            | ErrorKind::PreCheck(Status::ReceiptNotFound) => PossiblySubmitted,
+
+            ErrorKind::TransportError { not_sent, .. } if *not_sent => DefinitelyNotSubmitted,
+            ErrorKind::TransportError { .. } => PossiblySubmitted,
+
+            // the transaction reached consensus; sending it again would execute it again
+            ErrorKind::ReceiptStatus(_) => PossiblySubmitted,
+
+            // raised after the transaction was sent, while looking for or reading its outcome
+            ErrorKind::ReceiptMissingId { .. }
+            | ErrorKind::RecordNotGenerated(_)
+            | ErrorKind::ReceiptExpired(_)
+            | ErrorKind::InconsistentRecord(_)
+            | ErrorKind::ValidWindowElapsed(_)
+            | ErrorKind::ContractCall(_)
+            | ErrorKind::ContractDataTooShort { .. }
+            | ErrorKind::ContentMismatch { .. }
+            | ErrorKind::ShutdownTimeout { .. } => PossiblySubmitted,
+
+            // errors raised while building the transaction or by the node before submitting it
+            ErrorKind::MissingField(_)
+            | ErrorKind::Parse(_)
+            | ErrorKind::PreCheck(_)
+            | ErrorKind::InsufficientFee { .. }
+            | ErrorKind::InvalidEntityId { .. }
+            | ErrorKind::UnknownNode(_)
+            | ErrorKind::UnknownOperator(_)
+            | ErrorKind::OperatorKeyMismatch { .. }
+            | ErrorKind::WrongShardRealm { .. }
+            | ErrorKind::BudgetExhausted(_)
+            | ErrorKind::UnexpectedPayment
+            | ErrorKind::QueryCostExceeded { .. }
+            | ErrorKind::PaymentRequired { .. }
+            | ErrorKind::IgnoredSetters { .. }
+            | ErrorKind::InvalidSignature(_)
+            | ErrorKind::MissingSigner(_)
+            | ErrorKind::UnexpectedSigner(_)
+            | ErrorKind::UnsupportedTransactionKind(_)
+            | ErrorKind::UnsupportedQueryKind(_)
+            | ErrorKind::FeeRejected { .. }
+            | ErrorKind::InvalidFreezeTime { .. }
+            | ErrorKind::AutoRenewPeriodOutOfRange { .. }
+            | ErrorKind::InvalidTransferList(_)
+            | ErrorKind::NegativeRecordThreshold(_)
+            | ErrorKind::MemoTooLong(_)
+            | ErrorKind::ClientShutdown => DefinitelyNotSubmitted,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ErrorKind, TransactionState};
+
+    #[test]
+    fn test_budget_and_cost_errors_are_not_submitted() {
+        assert_eq!(
+            ErrorKind::BudgetExhausted("fees").transaction_state(),
+            TransactionState::DefinitelyNotSubmitted
+        );
+
+        assert_eq!(
+            ErrorKind::QueryCostExceeded { cost: 2, max: 1 }.transaction_state(),
+            TransactionState::DefinitelyNotSubmitted
+        );
+    }
 }
@@ -0,0 +1,71 @@
+use crate::proto;
+use chrono::{DateTime, Utc};
+
+/// The value of hbar in US cents used by the network to compute fees: `hbar_equiv` hbar
+/// are worth `cent_equiv` cents until `expiration_time`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExchangeRate {
+    pub hbar_equiv: i32,
+    pub cent_equiv: i32,
+    pub expiration_time: DateTime<Utc>,
+}
+
+impl ExchangeRate {
+    /// The value of `tinybars` in US cents at this rate.
+    pub fn to_cents(&self, tinybars: i64) -> f64 {
+        if self.hbar_equiv == 0 {
+            return 0.0;
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let hbar = tinybars as f64 / 100_000_000.0;
+
+        hbar * f64::from(self.cent_equiv) / f64::from(self.hbar_equiv)
+    }
+}
+
+impl From<proto::ExchangeRate::ExchangeRate> for ExchangeRate {
+    fn from(rate: proto::ExchangeRate::ExchangeRate) -> Self {
+        Self {
+            hbar_equiv: rate.get_hbarEquiv(),
+            cent_equiv: rate.get_centEquiv(),
+            expiration_time: rate.get_expirationTime().clone().into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ExchangeRate;
+    use crate::proto;
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn test_from_proto() {
+        let mut expiration = proto::Timestamp::TimestampSeconds::new();
+        expiration.set_seconds(1_568_592_000);
+
+        let mut rate = proto::ExchangeRate::ExchangeRate::new();
+        rate.set_hbarEquiv(1);
+        rate.set_centEquiv(12);
+        rate.set_expirationTime(expiration);
+
+        let rate = ExchangeRate::from(rate);
+
+        assert_eq!(rate.hbar_equiv, 1);
+        assert_eq!(rate.cent_equiv, 12);
+        assert_eq!(rate.expiration_time, Utc.timestamp(1_568_592_000, 0));
+    }
+
+    #[test]
+    fn test_to_cents() {
+        let rate = ExchangeRate {
+            hbar_equiv: 2,
+            cent_equiv: 25,
+            expiration_time: Utc::now(),
+        };
+
+        assert!((rate.to_cents(400_000_000) - 50.0).abs() < 1e-9);
+        assert!((rate.to_cents(83_000) - 0.010_375).abs() < 1e-9);
+    }
+}
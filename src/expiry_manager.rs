@@ -0,0 +1,356 @@
+use crate::{AccountId, Client, ContractId, ErrorKind, FileId, SecretKey, Status};
+use chrono::{DateTime, Utc};
+use failure::{err_msg, Error};
+use std::{
+    fmt,
+    sync::mpsc::{self, RecvTimeoutError},
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+/// An entity that expires unless its expiration time is extended.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExpiringEntity {
+    Account(AccountId),
+    File(FileId),
+    Contract(ContractId),
+}
+
+impl fmt::Display for ExpiringEntity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExpiringEntity::Account(id) => write!(f, "account {}", id),
+            ExpiringEntity::File(id) => write!(f, "file {}", id),
+            ExpiringEntity::Contract(id) => write!(f, "contract {}", id),
+        }
+    }
+}
+
+impl From<AccountId> for ExpiringEntity {
+    fn from(id: AccountId) -> Self {
+        ExpiringEntity::Account(id)
+    }
+}
+
+impl From<FileId> for ExpiringEntity {
+    fn from(id: FileId) -> Self {
+        ExpiringEntity::File(id)
+    }
+}
+
+impl From<ContractId> for ExpiringEntity {
+    fn from(id: ContractId) -> Self {
+        ExpiringEntity::Contract(id)
+    }
+}
+
+struct Tracked {
+    entity: ExpiringEntity,
+    expiration_time: DateTime<Utc>,
+    signer: Option<SecretKey>,
+}
+
+/// Keeps accounts, files and contracts alive by extending their expiration time
+/// before they expire.
+///
+/// Call `check` periodically, or `spawn` a thread that does so.
+pub struct ExpiryManager {
+    client: Client,
+    tracked: Vec<Tracked>,
+    renew_before: chrono::Duration,
+    extend_by: chrono::Duration,
+    on_renewed: Option<Box<dyn Fn(ExpiringEntity, DateTime<Utc>) + Send>>,
+    on_failed: Option<Box<dyn Fn(ExpiringEntity, &Error) + Send>>,
+}
+
+impl ExpiryManager {
+    pub fn new(client: &Client) -> Self {
+        Self {
+            client: client.clone(),
+            tracked: Vec::new(),
+            renew_before: chrono::Duration::days(1),
+            // ~3 months
+            extend_by: chrono::Duration::seconds(7_890_000),
+            on_renewed: None,
+            on_failed: None,
+        }
+    }
+
+    /// Renew entities that expire within `duration`. Defaults to 1 day.
+    ///
+    /// Fails if `duration` is too long to add to a time.
+    pub fn renew_before(&mut self, duration: Duration) -> Result<&mut Self, Error> {
+        self.renew_before = chrono::Duration::from_std(duration)?;
+        Ok(self)
+    }
+
+    /// Extend the expiration time by `duration` on each renewal. Defaults to ~3 months.
+    ///
+    /// Fails if `duration` is too long to add to a time.
+    pub fn extend_by(&mut self, duration: Duration) -> Result<&mut Self, Error> {
+        self.extend_by = chrono::Duration::from_std(duration)?;
+        Ok(self)
+    }
+
+    /// Call `callback` with the new expiration time after an entity is renewed.
+    pub fn on_renewed(
+        &mut self,
+        callback: impl Fn(ExpiringEntity, DateTime<Utc>) + Send + 'static,
+    ) -> &mut Self {
+        self.on_renewed = Some(Box::new(callback));
+        self
+    }
+
+    /// Call `callback` when renewing an entity fails, either before it is sent or because
+    /// its receipt has a status other than `Success`. The renewal is tried again on the
+    /// next check.
+    pub fn on_failed(
+        &mut self,
+        callback: impl Fn(ExpiringEntity, &Error) + Send + 'static,
+    ) -> &mut Self {
+        self.on_failed = Some(Box::new(callback));
+        self
+    }
+
+    /// Start tracking `entity`, fetching its current expiration time.
+    ///
+    /// Renewals are signed by the client operator only; use `track_signed` for entities
+    /// that need another key to sign updates (e.g. accounts other than the operator).
+    pub fn track(&mut self, entity: impl Into<ExpiringEntity>) -> Result<DateTime<Utc>, Error> {
+        self.add(entity.into(), None)
+    }
+
+    /// Start tracking `entity`, signing renewals with `secret` as well as the operator.
+    pub fn track_signed(
+        &mut self,
+        entity: impl Into<ExpiringEntity>,
+        secret: SecretKey,
+    ) -> Result<DateTime<Utc>, Error> {
+        self.add(entity.into(), Some(secret))
+    }
+
+    pub fn untrack(&mut self, entity: impl Into<ExpiringEntity>) {
+        let entity = entity.into();
+        self.tracked.retain(|tracked| tracked.entity != entity);
+    }
+
+    /// The tracked entities and the expiration time last seen for each.
+    pub fn tracked(&self) -> impl Iterator<Item = (ExpiringEntity, DateTime<Utc>)> + '_ {
+        self.tracked
+            .iter()
+            .map(|tracked| (tracked.entity, tracked.expiration_time))
+    }
+
+    /// Renew every tracked entity that is about to expire, returning how many were renewed.
+    ///
+    /// Each renewal waits for its receipt, and only counts once it shows the update
+    /// succeeded.
+    pub fn check(&mut self) -> usize {
+        let client = &self.client;
+        let now = client.clock.now();
+        let mut renewed = 0;
+
+        for tracked in &mut self.tracked {
+            if !due(tracked.expiration_time, self.renew_before, now) {
+                continue;
+            }
+
+            let result = tracked
+                .expiration_time
+                .checked_add_signed(self.extend_by)
+                .ok_or_else(|| err_msg("the new expiration time is out of range"))
+                .and_then(|expiration_time| {
+                    renew(client, tracked, expiration_time).map(|_| expiration_time)
+                });
+
+            match result {
+                Ok(expiration_time) => {
+                    tracked.expiration_time = expiration_time;
+                    renewed += 1;
+
+                    if let Some(on_renewed) = &self.on_renewed {
+                        on_renewed(tracked.entity, expiration_time);
+                    }
+                }
+
+                Err(error) => {
+                    log::warn!(
+                        target: "hedera::expiry_manager",
+                        "failed to renew {}: {}",
+                        tracked.entity,
+                        error
+                    );
+
+                    if let Some(on_failed) = &self.on_failed {
+                        on_failed(tracked.entity, &error);
+                    }
+                }
+            }
+        }
+
+        renewed
+    }
+
+    /// Move the manager to a new thread that calls `check` every `interval`, until the
+    /// returned handle is stopped or dropped.
+    pub fn spawn(mut self, interval: Duration) -> ExpiryManagerHandle {
+        let (stop, stopped) = mpsc::channel();
+
+        let thread = thread::spawn(move || loop {
+            self.check();
+
+            match stopped.recv_timeout(interval) {
+                Err(RecvTimeoutError::Timeout) => {}
+                _ => break,
+            }
+        });
+
+        ExpiryManagerHandle { stop, thread }
+    }
+
+    fn add(
+        &mut self,
+        entity: ExpiringEntity,
+        signer: Option<SecretKey>,
+    ) -> Result<DateTime<Utc>, Error> {
+        let expiration_time = match entity {
+            ExpiringEntity::Account(id) => self.client.account(id).info().get()?.expiration_time,
+            ExpiringEntity::File(id) => self.client.file(id).info().get()?.expiration_time,
+            ExpiringEntity::Contract(id) => self.client.contract(id).info().get()?.expiration_time,
+        };
+
+        self.untrack(entity);
+        self.tracked.push(Tracked {
+            entity,
+            expiration_time,
+            signer,
+        });
+
+        Ok(expiration_time)
+    }
+}
+
+/// The thread of an `ExpiryManager` started with `spawn`. Dropping the handle stops the
+/// thread as well, without waiting for it.
+#[must_use = "the manager stops when its handle is dropped"]
+pub struct ExpiryManagerHandle {
+    stop: mpsc::Sender<()>,
+    thread: JoinHandle<()>,
+}
+
+impl ExpiryManagerHandle {
+    /// Stop the manager, waiting for a check in progress to finish.
+    pub fn stop(self) {
+        // the thread stops when it next waits, whether or not it is waiting now
+        drop(self.stop);
+
+        // a panic in a callback has already been reported by the thread
+        self.thread.join().ok();
+    }
+}
+
+// Is an entity that expires at `expiration_time` to be renewed at `now`?
+fn due(expiration_time: DateTime<Utc>, renew_before: chrono::Duration, now: DateTime<Utc>) -> bool {
+    expiration_time
+        .checked_sub_signed(renew_before)
+        .map_or(true, |renew_at| renew_at <= now)
+}
+
+fn renew(client: &Client, tracked: &Tracked, expiration_time: DateTime<Utc>) -> Result<(), Error> {
+    // the transaction ID is returned once the transaction passed pre-check; it can still
+    // fail at consensus (e.g. if it is not signed by the keys of a file)
+    macro_rules! submit {
+        ($tx:expr) => {{
+            let mut tx = $tx;
+            tx.expires_at(expiration_time);
+
+            match &tracked.signer {
+                Some(secret) => tx.sign(secret).execute()?,
+                None => tx.execute()?,
+            }
+        }};
+    }
+
+    let id = match tracked.entity {
+        ExpiringEntity::Account(id) => submit!(client.account(id).update()),
+        ExpiringEntity::File(id) => submit!(client.file(id).update()),
+        ExpiringEntity::Contract(id) => submit!(client.contract(id).update()),
+    };
+
+    let receipt = client.transaction(id).wait_for_receipt()?;
+
+    if receipt.status != Status::Success {
+        Err(ErrorKind::ReceiptStatus(receipt.status))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{due, ExpiringEntity, ExpiryManager, Tracked};
+    use crate::{Client, Clock, FileId};
+    use chrono::{DateTime, Duration as ChronoDuration, TimeZone, Utc};
+    use failure::Error;
+    use futures::future::{self, BoxFuture};
+    use std::time::Duration;
+
+    struct FixedClock;
+
+    impl Clock for FixedClock {
+        fn now(&self) -> DateTime<Utc> {
+            Utc.timestamp(1_568_592_000, 0)
+        }
+
+        fn sleep(&self, _duration: Duration) -> BoxFuture<'static, ()> {
+            Box::pin(future::ready(()))
+        }
+    }
+
+    #[test]
+    fn test_due() {
+        let now = FixedClock.now();
+        let day = ChronoDuration::days(1);
+
+        assert!(due(now + ChronoDuration::hours(23), day, now));
+        assert!(due(now + day, day, now));
+        assert!(!due(now + ChronoDuration::hours(25), day, now));
+
+        // renewing that far ahead of the expiration time goes back beyond any time
+        assert!(due(now, ChronoDuration::max_value(), now));
+    }
+
+    #[test]
+    fn test_check_skips_entities_not_due() -> Result<(), Error> {
+        let client = Client::builder()
+            .node("0.0.3@127.0.0.1:50211".parse()?)
+            .clock(FixedClock)
+            .build()?;
+
+        let mut manager = ExpiryManager::new(&client);
+        manager.tracked.push(Tracked {
+            entity: ExpiringEntity::File(FileId::new(0, 0, 1001)),
+            expiration_time: FixedClock.now() + ChronoDuration::days(30),
+            signer: None,
+        });
+
+        assert_eq!(manager.check(), 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_reject_out_of_range_durations() -> Result<(), Error> {
+        let client = Client::builder()
+            .node("0.0.3@127.0.0.1:50211".parse()?)
+            .build()?;
+
+        let mut manager = ExpiryManager::new(&client);
+
+        assert!(manager.renew_before(Duration::from_secs(u64::max_value())).is_err());
+        assert!(manager.extend_by(Duration::from_secs(u64::max_value())).is_err());
+        assert!(manager.extend_by(Duration::from_secs(7_890_000)).is_ok());
+
+        Ok(())
+    }
+}
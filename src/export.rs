@@ -0,0 +1,237 @@
+//! Exporting query results for accounting and scripting (requires the `json` or `csv` features).
+
+#[cfg(feature = "json")]
+pub use self::json::ToJson;
+
+#[cfg(feature = "csv")]
+pub use self::csv::records_to_csv;
+
+#[cfg(feature = "json")]
+mod json {
+    use crate::{
+        function_result::ContractFunctionResult, AccountInfo, Claim, ContractInfo, ExchangeRate,
+        FileInfo, TransactionReceipt, TransactionRecord, TransactionRecordBody,
+    };
+    use serde_json::{json, Value};
+
+    /// Convert to a JSON value, with IDs and keys formatted as strings.
+    pub trait ToJson {
+        fn to_json(&self) -> Value;
+    }
+
+    impl<T: ToJson> ToJson for Vec<T> {
+        fn to_json(&self) -> Value {
+            self.iter().map(ToJson::to_json).collect()
+        }
+    }
+
+    impl ToJson for AccountInfo {
+        fn to_json(&self) -> Value {
+            json!({
+                "account_id": self.account_id.to_string(),
+                "contract_account_id": self.contract_account_id,
+                "deleted": self.deleted,
+                "proxy_account_id": self.proxy_account_id.as_ref().map(ToString::to_string),
+                "proxy_received": self.proxy_received,
+                "key": self.key.to_string(),
+                "balance": self.balance,
+                "generate_send_record_threshold": self.generate_send_record_threshold,
+                "generate_receive_record_threshold": self.generate_receive_record_threshold,
+                "receiver_signature_required": self.receiver_signature_required,
+                "expiration_time": self.expiration_time.to_rfc3339(),
+                "auto_renew_period": self.auto_renew_period.as_secs(),
+                "claims": self.claims.to_json(),
+            })
+        }
+    }
+
+    impl ToJson for Claim {
+        fn to_json(&self) -> Value {
+            json!({
+                "account_id": self.account.to_string(),
+                "hash": hex::encode(&self.hash),
+                "keys": self.keys.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            })
+        }
+    }
+
+    impl ToJson for ContractInfo {
+        fn to_json(&self) -> Value {
+            json!({
+                "contract_id": self.contract_id.to_string(),
+                "account_id": self.account_id.to_string(),
+                "contract_account_id": self.contract_account_id,
+                "admin_key": self.admin_key.as_ref().map(ToString::to_string),
+                "expiration_time": self.expiration_time.to_rfc3339(),
+                "auto_renew_period": self.auto_renew_period.as_secs(),
+                "storage": self.storage,
+            })
+        }
+    }
+
+    impl ToJson for FileInfo {
+        fn to_json(&self) -> Value {
+            json!({
+                "file_id": self.file_id.to_string(),
+                "size": self.size,
+                "expiration_time": self.expiration_time.to_rfc3339(),
+                "deleted": self.deleted,
+                "keys": self.keys.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            })
+        }
+    }
+
+    impl ToJson for TransactionReceipt {
+        fn to_json(&self) -> Value {
+            json!({
+                "status": format!("{:?}", self.status),
+                "account_id": self.account_id.as_ref().map(ToString::to_string),
+                "contract_id": self.contract_id.as_ref().map(ToString::to_string),
+                "file_id": self.file_id.as_ref().map(ToString::to_string),
+                "current_exchange_rate": self.current_exchange_rate.as_ref().map(ToJson::to_json),
+                "next_exchange_rate": self.next_exchange_rate.as_ref().map(ToJson::to_json),
+            })
+        }
+    }
+
+    impl ToJson for ExchangeRate {
+        fn to_json(&self) -> Value {
+            json!({
+                "hbar_equiv": self.hbar_equiv,
+                "cent_equiv": self.cent_equiv,
+                "expiration_time": self.expiration_time.to_rfc3339(),
+            })
+        }
+    }
+
+    impl ToJson for ContractFunctionResult {
+        fn to_json(&self) -> Value {
+            json!({
+                "contract_id": self.contract_id.to_string(),
+                "result": hex::encode(&self.contract_call_result),
+                "error_message": self.error_message,
+                "gas_used": self.gas_used,
+                "logs": self.log_info.iter().map(|log| json!({
+                    "contract_id": log.contract_id.to_string(),
+                    "topics": log.topic.iter().map(hex::encode).collect::<Vec<_>>(),
+                    "data": hex::encode(&log.data),
+                })).collect::<Vec<_>>(),
+            })
+        }
+    }
+
+    impl ToJson for TransactionRecord {
+        fn to_json(&self) -> Value {
+            let mut record = json!({
+                "transaction_id": self.transaction_id.to_string(),
+                "receipt": self.receipt.to_json(),
+                "transaction_hash": hex::encode(&self.transaction_hash),
+                "consensus_timestamp": self.consensus_timestamp.map(|time| time.to_rfc3339()),
+                "memo": self.memo.as_str(),
+                "transaction_fee": self.transaction_fee,
+            });
+
+            let (key, body) = match &self.body {
+                TransactionRecordBody::ContractCall(result) => ("contract_call", result.to_json()),
+                TransactionRecordBody::ContractCreate(result) => {
+                    ("contract_create", result.to_json())
+                }
+
+                TransactionRecordBody::Transfer(transfers) => (
+                    "transfers",
+                    transfers
+                        .iter()
+                        .map(|(account, amount)| {
+                            json!({ "account_id": account.to_string(), "amount": amount })
+                        })
+                        .collect(),
+                ),
+
+                TransactionRecordBody::Incomplete => ("incomplete", Value::Bool(true)),
+            };
+
+            record[key] = body;
+            record
+        }
+    }
+}
+
+#[cfg(feature = "csv")]
+mod csv {
+    use crate::{TransactionRecord, TransactionRecordBody};
+    use failure::Error;
+    use itertools::Itertools;
+    use std::io::Write;
+
+    /// Write one row per record, with the transfers of each record as
+    /// `account=amount` pairs separated by `;`.
+    ///
+    /// Memos that a spreadsheet would read as a formula are written with a leading `'`.
+    pub fn records_to_csv(writer: impl Write, records: &[TransactionRecord]) -> Result<(), Error> {
+        let mut writer = ::csv::Writer::from_writer(writer);
+
+        writer.write_record(&[
+            "transaction_id",
+            "consensus_timestamp",
+            "transaction_hash",
+            "status",
+            "transaction_fee",
+            "memo",
+            "transfers",
+        ])?;
+
+        for record in records {
+            let transfers = match &record.body {
+                TransactionRecordBody::Transfer(transfers) => transfers
+                    .iter()
+                    .map(|(account, amount)| format!("{}={}", account, amount))
+                    .join(";"),
+
+                _ => String::new(),
+            };
+
+            writer.write_record(&[
+                record.transaction_id.to_string(),
+                record
+                    .consensus_timestamp
+                    .map(|time| time.to_rfc3339())
+                    .unwrap_or_default(),
+                hex::encode(&record.transaction_hash),
+                format!("{:?}", record.receipt.status),
+                record.transaction_fee.to_string(),
+                spreadsheet_text(record.memo.to_string()),
+                transfers,
+            ])?;
+        }
+
+        writer.flush()?;
+
+        Ok(())
+    }
+
+    // Text that a spreadsheet would read as a formula (e.g. a memo of `=HYPERLINK(...)`) is
+    // prefixed with a quote, so that it is shown as text
+    fn spreadsheet_text(text: String) -> String {
+        match text.chars().next() {
+            Some('=') | Some('+') | Some('-') | Some('@') | Some('\t') | Some('\r') => {
+                format!("'{}", text)
+            }
+
+            _ => text,
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::spreadsheet_text;
+
+        #[test]
+        fn test_spreadsheet_text() {
+            assert_eq!(spreadsheet_text("=1+1".to_owned()), "'=1+1");
+            assert_eq!(spreadsheet_text("@SUM(A1)".to_owned()), "'@SUM(A1)");
+            assert_eq!(spreadsheet_text("-2".to_owned()), "'-2");
+            assert_eq!(spreadsheet_text("rent for May".to_owned()), "rent for May");
+            assert_eq!(spreadsheet_text(String::new()), "");
+        }
+    }
+}
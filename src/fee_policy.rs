@@ -0,0 +1,77 @@
+use crate::ErrorKind;
+use failure::Error;
+use std::sync::Arc;
+
+/// What to do with the fee of a transaction, as decided by the fee policy of the client.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FeeDecision {
+    /// Submit the transaction with the fee it was given.
+    Allow,
+
+    /// Lower the fee to at most this many tinybar.
+    Cap(u64),
+
+    /// Refuse to submit the transaction.
+    Reject,
+}
+
+/// Called with the kind of each transaction (as named in the HAPI protobufs, e.g.
+/// `CryptoTransfer`) and the fee it was given, before it is signed.
+pub(crate) type FeePolicy = Arc<dyn Fn(&str, u64) -> FeeDecision + Send + Sync>;
+
+// The fee to submit a transaction of `kind` with, under `policy`
+pub(crate) fn apply(
+    policy: Option<&FeePolicy>,
+    kind: &'static str,
+    fee: u64,
+) -> Result<u64, Error> {
+    let decision = match policy {
+        Some(policy) => policy(kind, fee),
+        None => return Ok(fee),
+    };
+
+    match decision {
+        FeeDecision::Allow => Ok(fee),
+        FeeDecision::Cap(max) => Ok(fee.min(max)),
+        FeeDecision::Reject => Err(ErrorKind::FeeRejected { kind, fee })?,
+    }
+}
+
+// Whether a transaction of `kind` that was signed with `fee` (e.g. one loaded from bytes)
+// may be submitted under `policy`; its fee can no longer be lowered, so a cap below it
+// rejects the transaction
+pub(crate) fn check(policy: Option<&FeePolicy>, kind: &'static str, fee: u64) -> Result<(), Error> {
+    if apply(policy, kind, fee)? < fee {
+        Err(ErrorKind::FeeRejected { kind, fee })?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply, check, FeeDecision, FeePolicy};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_apply() {
+        let policy: FeePolicy = Arc::new(|kind: &str, fee| match kind {
+            "CryptoTransfer" => FeeDecision::Cap(50_000_000),
+            "ContractCreate" if fee > 1_000_000_000 => FeeDecision::Reject,
+            _ => FeeDecision::Allow,
+        });
+
+        let policy = Some(&policy);
+
+        assert_eq!(apply(None, "CryptoTransfer", 100_000_000).ok(), Some(100_000_000));
+        assert_eq!(apply(policy, "CryptoTransfer", 100_000_000).ok(), Some(50_000_000));
+        assert_eq!(apply(policy, "CryptoTransfer", 10_000_000).ok(), Some(10_000_000));
+        assert_eq!(apply(policy, "ContractCreate", 500_000_000).ok(), Some(500_000_000));
+        assert!(apply(policy, "ContractCreate", 2_000_000_000).is_err());
+
+        assert!(check(None, "CryptoTransfer", 100_000_000).is_ok());
+        assert!(check(policy, "CryptoTransfer", 10_000_000).is_ok());
+        assert!(check(policy, "CryptoTransfer", 100_000_000).is_err());
+        assert!(check(policy, "ContractCreate", 2_000_000_000).is_err());
+    }
+}
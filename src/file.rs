@@ -0,0 +1,8 @@
+use crate::FileInfo;
+
+/// The information and contents of a file, fetched together.
+#[derive(Debug)]
+pub struct File {
+    pub info: FileInfo,
+    pub contents: Vec<u8>,
+}
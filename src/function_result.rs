@@ -1,9 +1,11 @@
 use crate::{
     id::ContractId,
     proto::{self},
+    ErrorKind,
 };
 use failure::Error;
 use hex;
+use sha3::{Digest, Keccak256};
 
 #[derive(Debug, Clone)]
 pub struct ContractLogInfo {
@@ -13,6 +15,74 @@ pub struct ContractLogInfo {
     pub data: Vec<u8>,
 }
 
+impl ContractLogInfo {
+    /// The first topic of logs emitted by the event with the given signature,
+    /// e.g. `Transfer(address,address,uint256)`.
+    pub fn event_topic(signature: &str) -> Vec<u8> {
+        let mut hasher = Keccak256::default();
+        hasher.input(signature.as_bytes());
+        hasher.result().to_vec()
+    }
+
+    /// Was this log emitted by the event with the signature hash `topic0`?
+    pub fn matches(&self, topic0: &[u8]) -> bool {
+        self.topic.first().map_or(false, |topic| topic.as_slice() == topic0)
+    }
+
+    /// An indexed parameter of the event as a raw 32-byte word.
+    ///
+    /// Indexed parameters start at topic 1; topic 0 is the event signature hash.
+    pub fn get_topic(&self, index: usize) -> Option<&[u8]> {
+        self.topic.get(index).map(Vec::as_slice)
+    }
+
+    pub fn get_topic_address(&self, index: usize) -> Option<Vec<u8>> {
+        self.get_topic(index)
+            .filter(|topic| topic.len() == 32)
+            .map(|topic| topic[12..].to_vec())
+    }
+
+    pub fn get_topic_long(&self, index: usize) -> Option<i64> {
+        self.get_topic(index)
+            .filter(|topic| topic.len() == 32)
+            .map(|topic| {
+                let mut bytes: [u8; 8] = Default::default();
+                bytes.copy_from_slice(&topic[24..]);
+                i64::from_be_bytes(bytes)
+            })
+    }
+
+    pub fn get_topic_bool(&self, index: usize) -> Option<bool> {
+        self.get_topic(index)
+            .filter(|topic| topic.len() == 32)
+            .map(|topic| topic[31] != 0)
+    }
+
+    /// The non-indexed parameters of the event, readable with the getters of
+    /// `ContractFunctionResult` (`get_int`, `get_string`, etc).
+    ///
+    /// `params` is the number of non-indexed parameters of the event; the data is checked to
+    /// hold a 32-byte word for each, so that reading a parameter of a static type (such as
+    /// `get_int` or `get_address`) below `params` cannot go past its end.
+    pub fn decode_data(&self, params: usize) -> Result<ContractFunctionResult, Error> {
+        if self.data.len() < params * 32 {
+            Err(ErrorKind::ContractDataTooShort {
+                expected: params * 32,
+                actual: self.data.len(),
+            })?;
+        }
+
+        Ok(ContractFunctionResult {
+            contract_id: self.contract_id,
+            contract_call_result: self.data.clone(),
+            error_message: String::new(),
+            bloom: Vec::new(),
+            gas_used: 0,
+            log_info: Vec::new(),
+        })
+    }
+}
+
 impl From<proto::ContractCallLocal::ContractLoginfo> for ContractLogInfo {
     fn from(mut log: proto::ContractCallLocal::ContractLoginfo) -> Self {
         Self {
@@ -35,6 +105,42 @@ pub struct ContractFunctionResult {
 }
 
 impl ContractFunctionResult {
+    /// The error the call failed with (e.g. a revert), if any.
+    pub fn as_error(&self) -> Option<ErrorKind> {
+        if self.error_message.is_empty() {
+            None
+        } else {
+            Some(ErrorKind::ContractCall(self.error_message.clone()))
+        }
+    }
+
+    /// The logs emitted by the event with the signature hash `signature_hash`
+    /// (see `ContractLogInfo::event_topic`).
+    pub fn logs_for_event<'a>(
+        &'a self,
+        signature_hash: &'a [u8],
+    ) -> impl Iterator<Item = &'a ContractLogInfo> + 'a {
+        self.log_info
+            .iter()
+            .filter(move |log| log.matches(signature_hash))
+    }
+
+    /// The fraction of `gas_limit` (the gas given to the call) that was used, or `None`
+    /// if `gas_limit` is zero.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn gas_used_ratio(&self, gas_limit: u64) -> Option<f64> {
+        if gas_limit == 0 {
+            return None;
+        }
+
+        Some(self.gas_used as f64 / gas_limit as f64)
+    }
+
+    /// The gas out of `gas_limit` that was not used.
+    pub fn gas_remaining(&self, gas_limit: u64) -> u64 {
+        gas_limit.saturating_sub(self.gas_used)
+    }
+
     fn get_byte_buffer(&self, offset: usize) -> u8 {
         self.contract_call_result[offset]
     }
@@ -123,4 +229,75 @@ impl From<proto::ContractCallLocal::ContractFunctionResult> for ContractFunction
             log_info: result.take_logInfo().into_iter().map(Into::into).collect(),
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ContractFunctionResult, ContractLogInfo};
+    use crate::ContractId;
+    use failure::Error;
+
+    fn log(topic: Vec<Vec<u8>>, data: Vec<u8>) -> ContractLogInfo {
+        ContractLogInfo {
+            contract_id: ContractId::new(0, 0, 1001),
+            bloom: Vec::new(),
+            topic,
+            data,
+        }
+    }
+
+    #[test]
+    fn test_error_and_gas() {
+        let mut result = log(Vec::new(), Vec::new()).decode_data(0).unwrap();
+        result.gas_used = 75_000;
+
+        assert!(result.as_error().is_none());
+        assert_eq!(result.gas_remaining(100_000), 25_000);
+        assert_eq!(result.gas_remaining(50_000), 0);
+        assert!((result.gas_used_ratio(100_000).unwrap() - 0.75).abs() < 1e-9);
+        assert!(result.gas_used_ratio(0).is_none());
+
+        result.error_message = "CONTRACT_REVERT_EXECUTED".to_owned();
+        assert!(result.as_error().is_some());
+    }
+
+    #[test]
+    fn test_event_topic() {
+        assert_eq!(
+            hex::encode(ContractLogInfo::event_topic("Transfer(address,address,uint256)")),
+            "ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"
+        );
+    }
+
+    #[test]
+    fn test_topics() {
+        let transfer = ContractLogInfo::event_topic("Transfer(address,address,uint256)");
+        let mut value = vec![0; 32];
+        value[31] = 42;
+
+        let log = log(vec![transfer.clone(), value, vec![1; 4]], Vec::new());
+
+        assert!(log.matches(&transfer));
+        assert_eq!(log.get_topic_long(1), Some(42));
+        assert_eq!(log.get_topic_bool(1), Some(true));
+        assert_eq!(log.get_topic_address(1), Some(vec![0; 20]));
+
+        // a topic that is not a full word, or is not there, is not read
+        assert_eq!(log.get_topic_long(2), None);
+        assert_eq!(log.get_topic_address(3), None);
+    }
+
+    #[test]
+    fn test_decode_short_data() -> Result<(), Error> {
+        let mut data = vec![0; 64];
+        data[31] = 1;
+
+        let result: ContractFunctionResult = log(Vec::new(), data.clone()).decode_data(2)?;
+        assert!(result.get_bool(0));
+
+        data.truncate(40);
+        assert!(log(Vec::new(), data).decode_data(2).is_err());
+
+        Ok(())
+    }
+}
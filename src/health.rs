@@ -0,0 +1,192 @@
+use crate::AccountId;
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+// Number of recent requests the stats of a node are computed over
+const WINDOW: usize = 100;
+
+// Number of requests a node must have seen before it can be judged unhealthy
+const MIN_REQUESTS: usize = 10;
+
+// Fraction of failed (or BUSY) requests at which a node is considered unhealthy
+const UNHEALTHY_RATE: f64 = 0.5;
+
+// How long an unhealthy node is skipped when picking the default node
+const COOL_DOWN: Duration = Duration::from_secs(30);
+
+/// How a node has been responding to recent requests, from `Client::node_health`.
+#[derive(Debug, Clone)]
+pub struct NodeHealth {
+    pub node: AccountId,
+
+    /// The number of recent requests the rates and latency are computed over.
+    pub requests: usize,
+
+    /// The fraction of requests that got no response from the node.
+    pub error_rate: f64,
+
+    /// The fraction of requests that the node responded to with BUSY.
+    pub busy_rate: f64,
+
+    /// The 95th percentile of the time the node took to respond.
+    pub latency_p95: Option<Duration>,
+
+    /// How much longer the node is skipped when picking the default node, if it is unhealthy.
+    pub cool_down: Option<Duration>,
+}
+
+/// The outcome of one request sent to a node.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Outcome {
+    /// The node responded, whatever the precheck status was.
+    Response,
+
+    /// The node responded with BUSY.
+    Busy,
+
+    /// The request failed without a response.
+    Failed,
+}
+
+/// Rolling stats of the requests sent to one node.
+#[derive(Debug, Default)]
+pub(crate) struct Stats {
+    requests: VecDeque<(Outcome, Duration)>,
+    cool_down_until: Option<Instant>,
+}
+
+impl Stats {
+    pub(crate) fn record(&mut self, outcome: Outcome, latency: Duration) {
+        if let Some(until) = self.cool_down_until {
+            if Instant::now() < until {
+                // requests sent explicitly to the node while it cools down
+                // do not extend the cool-down
+                return;
+            }
+
+            // the node gets a fresh start once the cool-down is over
+            self.cool_down_until = None;
+            self.requests.clear();
+        }
+
+        if self.requests.len() == WINDOW {
+            self.requests.pop_front();
+        }
+
+        self.requests.push_back((outcome, latency));
+
+        if self.requests.len() >= MIN_REQUESTS
+            && (self.rate(Outcome::Failed) >= UNHEALTHY_RATE
+                || self.rate(Outcome::Busy) >= UNHEALTHY_RATE)
+        {
+            self.cool_down_until = Some(Instant::now() + COOL_DOWN);
+        }
+    }
+
+    pub(crate) fn is_cooling_down(&self) -> bool {
+        self.cool_down().is_some()
+    }
+
+    pub(crate) fn health(&self, node: AccountId) -> NodeHealth {
+        NodeHealth {
+            node,
+            requests: self.requests.len(),
+            error_rate: self.rate(Outcome::Failed),
+            busy_rate: self.rate(Outcome::Busy),
+            latency_p95: self.latency_percentile(95),
+            cool_down: self.cool_down(),
+        }
+    }
+
+    fn cool_down(&self) -> Option<Duration> {
+        let now = Instant::now();
+
+        self.cool_down_until
+            .filter(|until| *until > now)
+            .map(|until| until - now)
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn rate(&self, outcome: Outcome) -> f64 {
+        if self.requests.is_empty() {
+            return 0.0;
+        }
+
+        let count = self
+            .requests
+            .iter()
+            .filter(|(candidate, _)| *candidate == outcome)
+            .count();
+
+        count as f64 / self.requests.len() as f64
+    }
+
+    // the latency of requests that got no response is not known
+    fn latency_percentile(&self, percentile: usize) -> Option<Duration> {
+        let mut latencies: Vec<_> = self
+            .requests
+            .iter()
+            .filter(|(outcome, _)| *outcome != Outcome::Failed)
+            .map(|(_, latency)| *latency)
+            .collect();
+
+        if latencies.is_empty() {
+            return None;
+        }
+
+        latencies.sort_unstable();
+
+        let rank = (latencies.len() * percentile + 99) / 100;
+
+        Some(latencies[rank - 1])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Outcome, Stats, MIN_REQUESTS};
+    use crate::AccountId;
+    use std::time::Duration;
+
+    #[test]
+    fn test_unhealthy_node_cools_down() {
+        let mut stats = Stats::default();
+
+        for i in 0..MIN_REQUESTS {
+            let outcome = if i % 2 == 0 {
+                Outcome::Busy
+            } else {
+                Outcome::Response
+            };
+
+            stats.record(outcome, Duration::from_millis(i as u64 * 10));
+        }
+
+        let health = stats.health(AccountId::new(0, 0, 3));
+
+        assert_eq!(health.requests, MIN_REQUESTS);
+        assert_eq!(health.busy_rate, 0.5);
+        assert_eq!(health.error_rate, 0.0);
+        assert_eq!(health.latency_p95, Some(Duration::from_millis(90)));
+        assert!(stats.is_cooling_down());
+    }
+
+    #[test]
+    fn test_healthy_node() {
+        let mut stats = Stats::default();
+
+        for _ in 0..MIN_REQUESTS {
+            stats.record(Outcome::Response, Duration::from_millis(5));
+        }
+
+        stats.record(Outcome::Failed, Duration::from_secs(5));
+
+        assert!(!stats.is_cooling_down());
+        assert_eq!(
+            stats.health(AccountId::new(0, 0, 3)).latency_p95,
+            Some(Duration::from_millis(5))
+        );
+    }
+}
@@ -1,6 +1,6 @@
 macro_rules! define_id {
     ($field:ident, $name:ident, $proto:ident, $method_set:ident, $method_get:ident) => {
-        #[derive(Debug, PartialEq, Clone, Copy)]
+        #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
         #[repr(C)]
         pub struct $name {
             pub shard: i64,
@@ -16,6 +16,61 @@ macro_rules! define_id {
                     $field,
                 }
             }
+
+            /// Create an ID, checking that every component is within the limits of the protocol.
+            pub fn try_new(shard: i64, realm: i64, $field: i64) -> Result<Self, failure::Error> {
+                let id = Self::new(shard, realm, $field);
+                id.validate()?;
+
+                Ok(id)
+            }
+
+            /// Check that no component is negative and that the shard fits in the 4 bytes
+            /// it is given in a Solidity address.
+            pub fn validate(&self) -> Result<(), failure::Error> {
+                let reason = if self.shard < 0 || self.realm < 0 || self.$field < 0 {
+                    "components must not be negative"
+                } else if self.shard > i64::from(i32::max_value()) {
+                    "shard must fit in 32 bits"
+                } else {
+                    return Ok(());
+                };
+
+                Err(crate::ErrorKind::InvalidEntityId {
+                    id: self.to_string(),
+                    reason,
+                })?
+            }
+
+            /// An ID in shard 0, realm 0, e.g. `0.0.3` from `from_num(3)`.
+            ///
+            /// Fails for numbers above `i64::MAX`, which do not fit the protocol.
+            pub fn from_num($field: u64) -> Result<Self, failure::Error> {
+                #[allow(clippy::cast_possible_wrap)]
+                let num = $field as i64;
+
+                if num < 0 {
+                    Err(crate::ErrorKind::InvalidEntityId {
+                        id: format!("0:0:{}", $field),
+                        reason: "number must fit in 63 bits",
+                    })?;
+                }
+
+                Ok(Self::new(0, 0, num))
+            }
+
+            /// An ID in the shard and realm of the network of `client`.
+            pub fn with_default_realm($field: i64, client: &crate::Client) -> Self {
+                Self::new(client.shard(), client.realm(), $field)
+            }
+        }
+
+        impl try_from::TryFrom<u64> for $name {
+            type Err = failure::Error;
+
+            fn try_from($field: u64) -> Result<Self, Self::Err> {
+                Self::from_num($field)
+            }
         }
 
         impl std::fmt::Display for $name {
@@ -37,7 +92,7 @@ macro_rules! define_id {
                     .next_tuple()
                     .ok_or_else(|| Parse("{shard}:{realm}:{num}"))?;
 
-                Ok(Self::new(shard?, realm?, $field?))
+                Self::try_new(shard?, realm?, $field?)
             }
         }
 
@@ -53,6 +108,9 @@ macro_rules! define_id {
 
         impl crate::proto::ToProto<crate::proto::BasicTypes::$proto> for $name {
             fn to_proto(&self) -> Result<crate::proto::BasicTypes::$proto, failure::Error> {
+                // `new` and the public fields skip validation; catch bad IDs before they are sent
+                self.validate()?;
+
                 let mut proto = crate::proto::BasicTypes::$proto::new();
                 proto.set_shardNum(self.shard);
                 proto.set_realmNum(self.realm);
@@ -81,3 +139,27 @@ define_id!(
     set_contractNum,
     get_contractNum
 );
+
+#[cfg(test)]
+mod tests {
+    use super::AccountId;
+    use try_from::TryFrom;
+
+    #[test]
+    fn test_parse_rejects_out_of_range() {
+        assert_eq!(
+            "0.0.3".parse::<AccountId>().unwrap(),
+            AccountId::new(0, 0, 3)
+        );
+
+        assert!("0:-1:3".parse::<AccountId>().is_err());
+        assert!("4294967296:0:3".parse::<AccountId>().is_err());
+        assert!(AccountId::try_new(0, 0, -3).is_err());
+    }
+
+    #[test]
+    fn test_from_num() {
+        assert_eq!(AccountId::try_from(3_u64).unwrap(), AccountId::new(0, 0, 3));
+        assert!(AccountId::from_num(u64::max_value()).is_err());
+    }
+}
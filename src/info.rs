@@ -21,6 +21,32 @@ pub struct AccountInfo {
     pub claims: Vec<Claim>,
 }
 
+/// Where an account is proxy staked, and how much is staked to it, from `AccountInfo::staking`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StakingInfo {
+    /// The account this account is proxy staked to. If it is `None` (or not a node, or refuses
+    /// proxy staking) the network stakes the account to a node of its choice, but the account
+    /// earns no payments.
+    pub staked_to: Option<AccountId>,
+
+    /// The balance of this account, in tinybars, which is what it stakes.
+    pub staked_balance: u64,
+
+    /// The tinybars proxy staked to this account by other accounts.
+    pub proxy_received: i64,
+}
+
+impl AccountInfo {
+    /// The staking of this account; change it with `client.account(id).stake_to(proxy)`.
+    pub fn staking(&self) -> StakingInfo {
+        StakingInfo {
+            staked_to: self.proxy_account_id,
+            staked_balance: self.balance,
+            proxy_received: self.proxy_received,
+        }
+    }
+}
+
 impl TryFrom<proto::CryptoGetInfo::CryptoGetInfoResponse_AccountInfo> for AccountInfo {
     type Err = Error;
 
@@ -115,3 +141,47 @@ impl TryFrom<proto::FileGetInfo::FileGetInfoResponse_FileInfo> for FileInfo {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{AccountInfo, ContractInfo, FileInfo};
+    use crate::proto;
+    use proptest::{collection::vec, prelude::*};
+    use protobuf::parse_from_bytes;
+    use try_from::TryFrom;
+
+    // whatever the bytes parse to, the conversions may fail but must not panic
+    proptest! {
+        #[test]
+        fn test_any_account_info_converts(bytes in vec(any::<u8>(), 0..512)) {
+            let info = parse_from_bytes::<proto::CryptoGetInfo::CryptoGetInfoResponse_AccountInfo>(
+                &bytes,
+            );
+
+            if let Ok(info) = info {
+                let _ = AccountInfo::try_from(info);
+            }
+        }
+
+        #[test]
+        fn test_any_contract_info_converts(bytes in vec(any::<u8>(), 0..512)) {
+            let info =
+                parse_from_bytes::<proto::ContractGetInfo::ContractGetInfoResponse_ContractInfo>(
+                    &bytes,
+                );
+
+            if let Ok(info) = info {
+                let _ = ContractInfo::try_from(info);
+            }
+        }
+
+        #[test]
+        fn test_any_file_info_converts(bytes in vec(any::<u8>(), 0..512)) {
+            let info = parse_from_bytes::<proto::FileGetInfo::FileGetInfoResponse_FileInfo>(&bytes);
+
+            if let Ok(info) = info {
+                let _ = FileInfo::try_from(info);
+            }
+        }
+    }
+}
@@ -1,44 +1,106 @@
+//! A client for the Hedera network.
+//!
+//! The public layout is stable:
+//!
+//! * [`transaction`]: every transaction builder, e.g. `hedera::transaction::TransactionFileCreate`
+//! * [`query`]: every query, e.g. `hedera::query::QueryFileGetInfo`
+//! * [`crypto`]: keys and signatures
+//! * [`client`]: the client and the partial messages returned by its methods
+//! * [`network`]: the IDs of system accounts and files, and the nodes of the public networks
+//!
+//! The types used most often (IDs, keys, receipts, records and errors) are also re-exported
+//! at the root. Files behind these modules can move without changing these paths.
+
 #![warn(clippy::pedantic, future_incompatible, unreachable_pub)]
 #![allow(clippy::stutter, clippy::new_ret_no_self, clippy::module_inception)]
 
 #[macro_use]
 mod macros;
 
+mod account_watch;
 mod argument;
+mod budget;
 pub mod call_params;
 mod call_param_utils;
 mod claim;
 pub mod client;
-mod crypto;
+mod client_stats;
+mod clock;
+mod contract_create_flow;
+pub mod crypto;
 mod duration;
 mod entity;
 mod error;
+mod exchange_rate;
+mod expiry_manager;
+mod fee_policy;
+pub mod export;
+mod file;
+mod health;
 mod id;
 mod info;
+mod memo;
+pub mod network;
+mod node_address;
+mod notarization;
+pub mod offline;
+mod payload_log;
+#[cfg(not(feature = "proto"))]
 mod proto;
+/// The generated protobufs of the Hedera API, to build the body of kinds of transaction this
+/// crate has no builder for yet; see `Client::custom_transaction`. No stability guarantee is
+/// made for this module, as it follows the `.proto` files.
+#[cfg(feature = "proto")]
+pub mod proto;
 pub mod query;
+mod retry;
+mod routing;
+mod signature_collector;
+mod shard_realm;
+mod signing;
 pub mod status;
 pub mod solidity_util;
+mod summary;
+pub mod testing;
 mod timestamp;
+mod trace;
 pub mod transaction;
 mod transaction_id;
 mod transaction_receipt;
 mod transaction_record;
+pub mod wallet;
 pub mod function_result;
 pub mod function_selector;
 
 pub use self::{
-    claim::Claim,
+    account_watch::{AccountInfoDelta, AccountSnapshot},
+    budget::Budget,
+    claim::{Claim, ClaimBuilder},
     client::Client,
+    client_stats::ClientStats,
+    clock::{Clock, SystemClock},
+    contract_create_flow::ContractCreateFlow,
     crypto::{PublicKey, SecretKey, Signature},
     entity::Entity,
-    error::ErrorKind,
+    error::{ErrorKind, TransactionState},
+    exchange_rate::ExchangeRate,
+    expiry_manager::{ExpiringEntity, ExpiryManager, ExpiryManagerHandle},
+    fee_policy::FeeDecision,
+    file::File,
+    health::NodeHealth,
     id::*,
-    info::{AccountInfo, ContractInfo, FileInfo},
+    info::{AccountInfo, ContractInfo, FileInfo, StakingInfo},
+    memo::Memo,
+    node_address::{Endpoint, NodeAddress},
+    notarization::Notarization,
+    payload_log::PayloadLogging,
+    routing::Service,
+    signature_collector::{RequiredKey, SignatureCollector},
     status::Status,
+    trace::{ExecutionTrace, TraceEvent, TraceStep},
     transaction_id::TransactionId,
     transaction_receipt::TransactionReceipt,
-    transaction_record::{TransactionRecord, TransactionRecordBody},
+    transaction_record::{TransactionRecord, TransactionRecordBody, TransferMismatch},
 };
 
 use once_cell::{sync::Lazy};
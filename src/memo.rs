@@ -0,0 +1,89 @@
+use crate::ErrorKind;
+use failure::Error;
+use std::fmt;
+
+/// The memo of a transaction.
+///
+/// Memos are UTF-8 text of at most `Memo::MAX_LEN` bytes on the network. Binary payloads
+/// are stored base64-encoded; see `from_bytes` and `decode_bytes`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Memo(String);
+
+impl Memo {
+    /// The maximum length in bytes of a memo, after any encoding.
+    pub const MAX_LEN: usize = 100;
+
+    /// A memo holding `bytes`, base64-encoded.
+    ///
+    /// At most 75 bytes fit in a memo once encoded.
+    pub fn from_bytes(bytes: impl AsRef<[u8]>) -> Self {
+        Memo(base64::encode(bytes.as_ref()))
+    }
+
+    /// Decode a memo that was made with `from_bytes`.
+    pub fn decode_bytes(&self) -> Result<Vec<u8>, Error> {
+        Ok(base64::decode(&self.0)?)
+    }
+
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    // Checked when the transaction is built, so that setting a memo stays infallible
+    pub(crate) fn check_len(&self) -> Result<(), Error> {
+        if self.0.len() > Self::MAX_LEN {
+            Err(ErrorKind::MemoTooLong(self.0.len()))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl From<String> for Memo {
+    fn from(text: String) -> Self {
+        Memo(text)
+    }
+}
+
+impl<'a> From<&'a str> for Memo {
+    fn from(text: &'a str) -> Self {
+        Memo(text.to_owned())
+    }
+}
+
+impl From<Memo> for String {
+    fn from(memo: Memo) -> Self {
+        memo.0
+    }
+}
+
+impl fmt::Display for Memo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Memo;
+    use failure::Error;
+
+    #[test]
+    fn test_bytes() -> Result<(), Error> {
+        let memo = Memo::from_bytes([0xde, 0xad, 0xbe, 0xef]);
+
+        assert_eq!(memo.as_str(), "3q2+7w==");
+        assert_eq!(memo.decode_bytes()?, vec![0xde, 0xad, 0xbe, 0xef]);
+        assert!(memo.check_len().is_ok());
+
+        assert!(Memo::from_bytes(vec![0; 76]).check_len().is_err());
+
+        Ok(())
+    }
+}
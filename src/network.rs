@@ -0,0 +1,481 @@
+//! Well-known entities of the Hedera networks, and the connections of a client to the nodes.
+//!
+//! Use these constants instead of writing out the IDs of system accounts and files.
+
+use crate::{
+    client_stats::ClientStats,
+    health::{NodeHealth, Outcome, Stats},
+    proto::{
+        CryptoService_grpc::CryptoServiceClient, FileService_grpc::FileServiceClient,
+        FreezeService_grpc::FreezeServiceClient,
+        SmartContractService_grpc::SmartContractServiceClient,
+    },
+    node_address::named_network,
+    payload_log::PayloadLogging,
+    AccountId, ErrorKind, FileId, NodeAddress,
+};
+use failure::Error;
+use grpc::ClientStub;
+use parking_lot::{Condvar, Mutex};
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+/// The account that holds the hbar not in any other account, and collects fees.
+pub const TREASURY: AccountId = AccountId {
+    shard: 0,
+    realm: 0,
+    account: 2,
+};
+
+/// The file with the address book of the nodes (a serialized `NodeAddressBook`); see
+/// `NodeAddress::from_address_book` and `Client::address_book`.
+pub const ADDRESS_BOOK_FILE: FileId = FileId {
+    shard: 0,
+    realm: 0,
+    file: 102,
+};
+
+/// The file with the current fee schedule.
+pub const FEE_SCHEDULE: FileId = FileId {
+    shard: 0,
+    realm: 0,
+    file: 111,
+};
+
+/// The file with the current and next exchange rates between hbar and cents.
+pub const EXCHANGE_RATES: FileId = FileId {
+    shard: 0,
+    realm: 0,
+    file: 112,
+};
+
+/// The shortest auto-renew period the network accepts for accounts and contracts.
+pub const MIN_AUTO_RENEW_PERIOD: Duration = Duration::from_secs(6_999_999);
+
+/// The longest auto-renew period the network accepts for accounts and contracts.
+pub const MAX_AUTO_RENEW_PERIOD: Duration = Duration::from_secs(8_000_001);
+
+/// The port nodes accept requests on unless their address says otherwise.
+pub const DEFAULT_PORT: u16 = 50211;
+
+/// The nodes of a public network: `mainnet`, `testnet` or `previewnet`.
+pub fn default_nodes(network: &str) -> Option<Vec<NodeAddress>> {
+    named_network(network)
+}
+
+/// Settings for the connections to each node.
+#[derive(Debug, Clone)]
+pub(crate) struct ConnectionConfig {
+    pub(crate) connection_timeout: Duration,
+    pub(crate) idle_reconnect_interval: Option<Duration>,
+    pub(crate) max_frame_size: Option<u32>,
+    pub(crate) no_delay: bool,
+}
+
+impl Default for ConnectionConfig {
+    fn default() -> Self {
+        Self {
+            connection_timeout: Duration::from_secs(5),
+            idle_reconnect_interval: None,
+            max_frame_size: None,
+            no_delay: true,
+        }
+    }
+}
+
+/// The service clients of a connection to one node.
+pub(crate) struct Channel {
+    pub(crate) crypto: CryptoServiceClient,
+    pub(crate) file: FileServiceClient,
+    pub(crate) contract: SmartContractServiceClient,
+    pub(crate) freeze: FreezeServiceClient,
+}
+
+impl Channel {
+    // Set up a client for the first endpoint of `address`, from the one at `start` on, that
+    // one can be set up for, and return it with the index of its endpoint.
+    //
+    // This does not connect yet: the connection is made by the first request, so an endpoint
+    // that cannot be reached is only found out then, and `Network::reconnect` moves on to
+    // the next endpoint.
+    fn connect(
+        address: &NodeAddress,
+        start: usize,
+        config: &ConnectionConfig,
+    ) -> Result<(Self, usize), Error> {
+        let count = address.endpoints.len();
+        let mut last_error = None;
+
+        for index in (start..start + count).map(|index| index % count) {
+            let endpoint = &address.endpoints[index];
+            let client = grpc::Client::new_plain(
+                &endpoint.host,
+                endpoint.port,
+                grpc::ClientConf {
+                    http: httpbis::ClientConf {
+                        no_delay: Some(config.no_delay),
+                        connection_timeout: Some(config.connection_timeout),
+                        common: httpbis::CommonConf {
+                            max_frame_size: config.max_frame_size,
+                            ..httpbis::CommonConf::default()
+                        },
+                        ..httpbis::ClientConf::default()
+                    },
+                },
+            );
+
+            match client {
+                Ok(client) => {
+                    let client = Arc::new(client);
+                    let channel = Self {
+                        crypto: CryptoServiceClient::with_client(client.clone()),
+                        file: FileServiceClient::with_client(client.clone()),
+                        contract: SmartContractServiceClient::with_client(client.clone()),
+                        freeze: FreezeServiceClient::with_client(client),
+                    };
+
+                    return Ok((channel, index));
+                }
+
+                Err(error) => {
+                    log::warn!(
+                        target: "hedera::network",
+                        "failed to connect to {} ({}): {}",
+                        address.account_id,
+                        endpoint,
+                        error
+                    );
+
+                    last_error = Some(error);
+                }
+            }
+        }
+
+        Err(match last_error {
+            // the connection could not be set up, so no request was sent
+            Some(error) => ErrorKind::TransportError {
+                code: None,
+                message: error.to_string(),
+                not_sent: true,
+            }
+            .into(),
+            None => ErrorKind::MissingField("endpoints").into(),
+        })
+    }
+}
+
+struct Connection {
+    channel: Arc<Channel>,
+
+    // the index of the endpoint of the node the channel is for
+    endpoint: usize,
+
+    last_used: Instant,
+}
+
+struct Node {
+    address: NodeAddress,
+
+    // `None` once the network is shut down
+    connection: Mutex<Option<Connection>>,
+
+    stats: Mutex<Stats>,
+}
+
+/// The nodes a client can send requests to, and a connection to each of them.
+pub(crate) struct Network {
+    nodes: Vec<Node>,
+    config: ConnectionConfig,
+    closed: AtomicBool,
+
+    // the number of `Request` guards alive, for the shutdown to wait on
+    in_flight: Mutex<usize>,
+
+    // notified when the last `Request` guard is dropped
+    drained: Condvar,
+
+    // shared by every clone of the client, like the connections
+    pub(crate) stats: Mutex<ClientStats>,
+
+    pub(crate) payload_logging: PayloadLogging,
+
+    // set by `ClientBuilder::strict_payments`
+    pub(crate) strict_payments: bool,
+}
+
+impl Network {
+    pub(crate) fn connect(
+        addresses: Vec<NodeAddress>,
+        config: ConnectionConfig,
+    ) -> Result<Self, Error> {
+        if addresses.is_empty() {
+            return Err(ErrorKind::MissingField("node"))?;
+        }
+
+        let nodes = addresses
+            .into_iter()
+            .map(|address| {
+                let (channel, endpoint) = Channel::connect(&address, 0, &config)?;
+
+                Ok(Node {
+                    address,
+                    connection: Mutex::new(Some(Connection {
+                        channel: Arc::new(channel),
+                        endpoint,
+                        last_used: Instant::now(),
+                    })),
+                    stats: Mutex::new(Stats::default()),
+                })
+            })
+            .collect::<Result<_, Error>>()?;
+
+        Ok(Self {
+            nodes,
+            config,
+            closed: AtomicBool::new(false),
+            in_flight: Mutex::new(0),
+            drained: Condvar::new(),
+            stats: Mutex::new(ClientStats::default()),
+            payload_logging: PayloadLogging::default(),
+            strict_payments: false,
+        })
+    }
+
+    /// The node used when none is specified on the client or the request: the first node
+    /// that is not cooling down after failing too many recent requests.
+    pub(crate) fn default_node(&self) -> AccountId {
+        let node = self
+            .nodes
+            .iter()
+            .find(|node| !node.stats.lock().is_cooling_down())
+            .unwrap_or(&self.nodes[0]);
+
+        node.address.account_id
+    }
+
+    /// The first of `candidates` that is not cooling down, or else the first candidate.
+    pub(crate) fn pick_node(&self, candidates: &[AccountId]) -> Option<AccountId> {
+        candidates
+            .iter()
+            .find(|candidate| {
+                self.node(Some(**candidate))
+                    .map(|node| !node.stats.lock().is_cooling_down())
+                    .unwrap_or(false)
+            })
+            .or_else(|| candidates.first())
+            .copied()
+    }
+
+    /// Record the outcome of a request sent to `node`, for choosing the default node.
+    pub(crate) fn record(&self, node: Option<AccountId>, outcome: Outcome, latency: Duration) {
+        if let Ok(node) = self.node(node) {
+            node.stats.lock().record(outcome, latency);
+        }
+    }
+
+    pub(crate) fn health(&self) -> Vec<NodeHealth> {
+        self.nodes
+            .iter()
+            .map(|node| node.stats.lock().health(node.address.account_id))
+            .collect()
+    }
+
+    fn node(&self, node: Option<AccountId>) -> Result<&Node, Error> {
+        let node = node.unwrap_or_else(|| self.default_node());
+
+        self.nodes
+            .iter()
+            .find(|candidate| candidate.address.account_id == node)
+            .ok_or_else(|| ErrorKind::UnknownNode(node).into())
+    }
+
+    /// Count a request as in flight until the returned guard is dropped; fails once the
+    /// network is shut down.
+    pub(crate) fn start_request(self: &Arc<Self>) -> Result<Request, Error> {
+        // count the request before checking, so that a shutdown that starts in between
+        // waits for it
+        *self.in_flight.lock() += 1;
+        let request = Request(self.clone());
+
+        if self.closed.load(Ordering::SeqCst) {
+            Err(ErrorKind::ClientShutdown)?;
+        }
+
+        Ok(request)
+    }
+
+    pub(crate) fn channel(&self, node: Option<AccountId>) -> Result<Arc<Channel>, Error> {
+        if self.closed.load(Ordering::SeqCst) {
+            Err(ErrorKind::ClientShutdown)?;
+        }
+
+        let node = self.node(node)?;
+        let mut connection = node.connection.lock();
+        let connection = connection.as_mut().ok_or(ErrorKind::ClientShutdown)?;
+
+        if let Some(interval) = self.config.idle_reconnect_interval {
+            // the transport does not send pings; assume a connection that has been idle for
+            // longer than the interval was dropped and establish a new one
+            if connection.last_used.elapsed() > interval {
+                let (channel, endpoint) =
+                    Channel::connect(&node.address, connection.endpoint, &self.config)?;
+
+                connection.channel = Arc::new(channel);
+                connection.endpoint = endpoint;
+            }
+        }
+
+        connection.last_used = Instant::now();
+
+        Ok(connection.channel.clone())
+    }
+
+    /// Replace the connection to `node` after `broken` broke with one to its next endpoint,
+    /// so that later requests (including from other clones of the client) use the new
+    /// connection.
+    ///
+    /// Requests that started before a shutdown may still reconnect while it waits for them.
+    pub(crate) fn reconnect(
+        &self,
+        node: Option<AccountId>,
+        broken: &Arc<Channel>,
+    ) -> Result<Arc<Channel>, Error> {
+        let node = self.node(node)?;
+        let mut connection = node.connection.lock();
+        let connection = connection.as_mut().ok_or(ErrorKind::ClientShutdown)?;
+
+        // another request saw the same connection break and already replaced it
+        if !Arc::ptr_eq(&connection.channel, broken) {
+            return Ok(connection.channel.clone());
+        }
+
+        log::warn!(target: "hedera::network", "reconnecting to {}", node.address.account_id);
+
+        // the endpoint in use may be the one that is down, so fail over to the next one
+        let (channel, endpoint) =
+            Channel::connect(&node.address, connection.endpoint + 1, &self.config)?;
+
+        connection.channel = Arc::new(channel);
+        connection.endpoint = endpoint;
+        connection.last_used = Instant::now();
+
+        Ok(connection.channel.clone())
+    }
+
+    /// Refuse new requests, wait up to `timeout` for the requests in flight to finish and
+    /// then close the connection to every node.
+    pub(crate) fn shutdown(&self, timeout: Duration) -> Result<(), Error> {
+        self.closed.store(true, Ordering::SeqCst);
+
+        let deadline = Instant::now() + timeout;
+        let mut in_flight = self.in_flight.lock();
+
+        while *in_flight > 0 {
+            if self.drained.wait_until(&mut in_flight, deadline).timed_out() {
+                break;
+            }
+        }
+
+        let pending = *in_flight;
+        drop(in_flight);
+
+        for node in &self.nodes {
+            // requests still in flight keep their own handle to the connection
+            node.connection.lock().take();
+        }
+
+        if pending > 0 {
+            Err(ErrorKind::ShutdownTimeout { pending })?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A request in flight on the network, from `Network::start_request`.
+pub(crate) struct Request(Arc<Network>);
+
+impl Drop for Request {
+    fn drop(&mut self) {
+        let mut in_flight = self.0.in_flight.lock();
+        *in_flight -= 1;
+
+        if *in_flight == 0 {
+            self.0.drained.notify_all();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ConnectionConfig, Network};
+    use crate::{AccountId, Endpoint, NodeAddress};
+    use failure::Error;
+    use std::{sync::Arc, thread, time::Duration};
+
+    #[test]
+    fn test_reconnect_fails_over_to_next_endpoint() -> Result<(), Error> {
+        let node = AccountId::new(0, 0, 3);
+        let address = NodeAddress::new(node, Endpoint::new("127.0.0.1", 50211))
+            .endpoint(Endpoint::new("127.0.0.1", 50212));
+
+        let network = Network::connect(vec![address], ConnectionConfig::default())?;
+        let endpoint = || network.nodes[0].connection.lock().as_ref().unwrap().endpoint;
+
+        let first = network.channel(Some(node))?;
+        assert_eq!(endpoint(), 0);
+
+        let second = network.reconnect(Some(node), &first)?;
+        assert_eq!(endpoint(), 1);
+
+        // a request that saw the first connection break as well keeps the second one
+        network.reconnect(Some(node), &first)?;
+        assert_eq!(endpoint(), 1);
+
+        network.reconnect(Some(node), &second)?;
+        assert_eq!(endpoint(), 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_shutdown_waits_for_requests_in_flight() -> Result<(), Error> {
+        let address = NodeAddress::new(AccountId::new(0, 0, 3), Endpoint::new("127.0.0.1", 50211));
+        let network = Arc::new(Network::connect(vec![address], ConnectionConfig::default())?);
+
+        // a handle to a connection alone is not a request
+        let _channel = network.channel(None)?;
+        let request = network.start_request()?;
+
+        assert!(network.shutdown(Duration::from_millis(20)).is_err());
+        assert!(network.start_request().is_err());
+
+        drop(request);
+        assert!(network.shutdown(Duration::from_millis(20)).is_ok());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_shutdown_wakes_when_last_request_finishes() -> Result<(), Error> {
+        let address = NodeAddress::new(AccountId::new(0, 0, 3), Endpoint::new("127.0.0.1", 50211));
+        let network = Arc::new(Network::connect(vec![address], ConnectionConfig::default())?);
+        let request = network.start_request()?;
+
+        let finished = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(20));
+            drop(request);
+        });
+
+        // returns as soon as the request is dropped, well before the timeout
+        network.shutdown(Duration::from_secs(60))?;
+        finished.join().unwrap();
+
+        Ok(())
+    }
+}
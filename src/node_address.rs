@@ -0,0 +1,249 @@
+use crate::{network::DEFAULT_PORT, proto, AccountId, ErrorKind};
+use failure::Error;
+use itertools::Itertools;
+use protobuf::parse_from_bytes;
+use std::{fmt, str::FromStr};
+use try_from::TryFrom;
+
+/// A `host:port` pair that a node accepts gRPC connections on.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Endpoint {
+    pub host: String,
+    pub port: u16,
+}
+
+impl Endpoint {
+    pub fn new(host: impl Into<String>, port: u16) -> Self {
+        Self {
+            host: host.into(),
+            port,
+        }
+    }
+}
+
+impl fmt::Display for Endpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.host, self.port)
+    }
+}
+
+impl FromStr for Endpoint {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (host, port) = s
+            .rsplitn(2, ':')
+            .next_tuple()
+            .map(|(port, host)| (host, port))
+            .ok_or_else(|| ErrorKind::Parse("{host}:{port}"))?;
+
+        Ok(Self::new(host, port.parse()?))
+    }
+}
+
+/// The account of a node on the network together with every endpoint it can be reached on.
+///
+/// The first endpoint is used until a request to it fails to reach the node; the request is
+/// then sent again to the next endpoint, which later requests go on to use.
+#[derive(Debug, PartialEq, Clone)]
+pub struct NodeAddress {
+    pub account_id: AccountId,
+    pub endpoints: Vec<Endpoint>,
+}
+
+impl NodeAddress {
+    pub fn new(account_id: AccountId, endpoint: Endpoint) -> Self {
+        Self {
+            account_id,
+            endpoints: vec![endpoint],
+        }
+    }
+
+    /// Add another endpoint to fall back to if the ones before it cannot be reached.
+    pub fn endpoint(mut self, endpoint: Endpoint) -> Self {
+        self.endpoints.push(endpoint);
+        self
+    }
+
+    /// Parse the contents of an address book file (a serialized `NodeAddressBook`).
+    ///
+    /// Entries that share a node account are merged into one address with multiple endpoints.
+    pub fn from_address_book(bytes: &[u8]) -> Result<Vec<Self>, Error> {
+        let mut book: proto::BasicTypes::NodeAddressBook = parse_from_bytes(bytes)?;
+        let mut addresses: Vec<Self> = Vec::new();
+
+        for address in book.take_nodeAddress().into_iter() {
+            let address = Self::try_from(address)?;
+
+            match addresses
+                .iter_mut()
+                .find(|existing| existing.account_id == address.account_id)
+            {
+                Some(existing) => existing.endpoints.extend(address.endpoints),
+                None => addresses.push(address),
+            }
+        }
+
+        Ok(addresses)
+    }
+}
+
+/// Parse a node as `{account}@{host}:{port}`, e.g. `0.0.3@35.237.200.180:50211`.
+impl FromStr for NodeAddress {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (account_id, endpoint) = s
+            .splitn(2, '@')
+            .next_tuple()
+            .ok_or_else(|| ErrorKind::Parse("{account}@{host}:{port}"))?;
+
+        Ok(Self::new(account_id.parse()?, endpoint.parse()?))
+    }
+}
+
+// The nodes of the public networks, by the names the Hedera SDKs use for them
+const MAINNET: &[(u64, &str)] = &[
+    (3, "35.237.200.180"),
+    (4, "35.186.191.247"),
+    (5, "35.192.2.25"),
+    (6, "35.199.161.108"),
+    (7, "35.203.82.240"),
+    (8, "35.236.5.219"),
+    (9, "35.197.192.225"),
+    (10, "35.242.233.154"),
+    (11, "35.240.118.96"),
+    (12, "35.204.86.32"),
+];
+
+const TESTNET: &[(u64, &str)] = &[
+    (3, "0.testnet.hedera.com"),
+    (4, "1.testnet.hedera.com"),
+    (5, "2.testnet.hedera.com"),
+    (6, "3.testnet.hedera.com"),
+];
+
+const PREVIEWNET: &[(u64, &str)] = &[
+    (3, "0.previewnet.hedera.com"),
+    (4, "1.previewnet.hedera.com"),
+    (5, "2.previewnet.hedera.com"),
+    (6, "3.previewnet.hedera.com"),
+];
+
+/// The nodes of a public network: `mainnet`, `testnet` or `previewnet`.
+pub(crate) fn named_network(name: &str) -> Option<Vec<NodeAddress>> {
+    let nodes = match name {
+        "mainnet" => MAINNET,
+        "testnet" => TESTNET,
+        "previewnet" => PREVIEWNET,
+        _ => return None,
+    };
+
+    Some(
+        nodes
+            .iter()
+            .map(|(num, host)| {
+                let endpoint = Endpoint::new(*host, DEFAULT_PORT);
+                NodeAddress::new(AccountId::new(0, 0, *num), endpoint)
+            })
+            .collect(),
+    )
+}
+
+impl TryFrom<proto::BasicTypes::NodeAddress> for NodeAddress {
+    type Err = Error;
+
+    fn try_from(mut address: proto::BasicTypes::NodeAddress) -> Result<Self, Error> {
+        // the node account is stored in the memo as `{shard}.{realm}.{num}`
+        let account_id = String::from_utf8(address.take_memo())?.trim().parse()?;
+        let host = String::from_utf8(address.take_ipAddress())?;
+
+        let port = match address.get_portno() {
+            0 => DEFAULT_PORT,
+            port => u16::try_from(port).map_err(|_| ErrorKind::Parse("u16 port"))?,
+        };
+
+        Ok(Self::new(account_id, Endpoint::new(host.trim(), port)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{named_network, Endpoint, NodeAddress};
+    use crate::{proto, AccountId};
+    use failure::Error;
+    use protobuf::{Message, RepeatedField};
+    use try_from::TryFrom;
+
+    #[test]
+    fn test_parse_endpoint() -> Result<(), Error> {
+        let endpoint: Endpoint = "testnet.hedera.com:50211".parse()?;
+
+        assert_eq!(endpoint, Endpoint::new("testnet.hedera.com", 50211));
+        assert_eq!(endpoint.to_string(), "testnet.hedera.com:50211");
+        assert!("testnet.hedera.com".parse::<Endpoint>().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_node_address() -> Result<(), Error> {
+        let address: NodeAddress = "0.0.3@35.237.200.180:50211".parse()?;
+
+        assert_eq!(
+            address,
+            NodeAddress::new(
+                AccountId::new(0, 0, 3),
+                Endpoint::new("35.237.200.180", 50211)
+            )
+        );
+
+        assert!("35.237.200.180:50211".parse::<NodeAddress>().is_err());
+        assert_eq!(named_network("testnet").map(|nodes| nodes.len()), Some(4));
+        assert!(named_network("localnet").is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_address_book_merges_endpoints() -> Result<(), Error> {
+        let entry = |ip: &str, memo: &str| {
+            let mut address = proto::BasicTypes::NodeAddress::new();
+            address.set_ipAddress(ip.as_bytes().to_vec());
+            address.set_portno(50211);
+            address.set_memo(memo.as_bytes().to_vec());
+            address
+        };
+
+        let mut book = proto::BasicTypes::NodeAddressBook::new();
+        book.set_nodeAddress(RepeatedField::from_vec(vec![
+            entry("35.237.200.180", "0.0.3"),
+            entry("35.186.191.247", "0.0.4"),
+            entry("34.239.82.6", "0.0.3"),
+        ]));
+
+        let addresses = NodeAddress::from_address_book(&book.write_to_bytes()?)?;
+
+        assert_eq!(addresses.len(), 2);
+        assert_eq!(addresses[0].account_id, AccountId::new(0, 0, 3));
+        assert_eq!(
+            addresses[0].endpoints,
+            vec![
+                Endpoint::new("35.237.200.180", 50211),
+                Endpoint::new("34.239.82.6", 50211),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_out_of_range_port_is_rejected() {
+        let mut address = proto::BasicTypes::NodeAddress::new();
+        address.set_ipAddress(b"35.237.200.180".to_vec());
+        address.set_portno(70000);
+        address.set_memo(b"0.0.3".to_vec());
+
+        assert!(NodeAddress::try_from(address).is_err());
+    }
+}
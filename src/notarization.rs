@@ -0,0 +1,46 @@
+use crate::FileId;
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha384};
+
+/// Proof that a payload was notarized with `Client::notarize`: its hash was stored in a
+/// file on the network, and that transaction reached consensus at `consensus_time`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Notarization {
+    pub file_id: FileId,
+
+    /// The SHA-384 hash of the payload; this is also the contents of the file.
+    pub hash: Vec<u8>,
+
+    pub consensus_time: DateTime<Utc>,
+}
+
+impl Notarization {
+    /// Is `bytes` the payload that was notarized?
+    pub fn verify(&self, bytes: impl AsRef<[u8]>) -> bool {
+        hash(bytes.as_ref()) == self.hash
+    }
+}
+
+pub(crate) fn hash(bytes: &[u8]) -> Vec<u8> {
+    Sha384::digest(bytes).to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{hash, Notarization};
+    use crate::FileId;
+    use chrono::Utc;
+
+    #[test]
+    fn test_verify() {
+        let notarization = Notarization {
+            file_id: FileId::new(0, 0, 1001),
+            hash: hash(b"hello"),
+            consensus_time: Utc::now(),
+        };
+
+        assert_eq!(notarization.hash.len(), 48);
+        assert!(notarization.verify(b"hello"));
+        assert!(!notarization.verify(b"hello!"));
+    }
+}
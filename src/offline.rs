@@ -0,0 +1,136 @@
+//! Signing transactions on a machine that is not connected to the network.
+//!
+//! On the online machine, freeze a transaction and export it as an [`UnsignedTransaction`].
+//! Carry the bytes (or JSON, with the `json` feature) to the offline machine, check the
+//! [`summary`](UnsignedTransaction::summary), and [`sign`](UnsignedTransaction::sign) it.
+//! Carry the resulting [`DetachedSignature`] back and attach it with
+//! [`Transaction::add_signature`].
+
+use crate::{
+    crypto::{PublicKey, SecretKey, Signature},
+    proto, summary,
+    transaction::{Transaction, TransactionRaw},
+    ErrorKind,
+};
+use failure::Error;
+use protobuf::parse_from_bytes;
+use std::{fmt, str::FromStr};
+
+/// The body of a transaction waiting to be signed offline.
+pub struct UnsignedTransaction {
+    body: proto::TransactionBody::TransactionBody,
+    bytes: Vec<u8>,
+}
+
+impl UnsignedTransaction {
+    pub fn new<T: 'static>(
+        transaction: &mut Transaction<T, TransactionRaw>,
+    ) -> Result<Self, Error> {
+        match transaction.body_bytes() {
+            Some(bytes) => Self::from_bytes(bytes),
+
+            // the transaction failed to build; take the error out to report it
+            None => Err(transaction.take_raw().err().unwrap()),
+        }
+    }
+
+    /// The serialized transaction body; the bytes that are signed.
+    pub fn to_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn from_bytes(bytes: impl AsRef<[u8]>) -> Result<Self, Error> {
+        let bytes = bytes.as_ref().to_vec();
+        let body = parse_from_bytes(&bytes)?;
+
+        Ok(Self { body, bytes })
+    }
+
+    /// A description of the transaction, decoded from the body itself, to check before signing.
+    pub fn summary(&self) -> String {
+        summary::summarize(&self.body)
+    }
+
+    pub fn sign(&self, secret: &SecretKey) -> DetachedSignature {
+        DetachedSignature {
+            public: secret.public(),
+            signature: secret.sign(&self.bytes),
+        }
+    }
+
+    #[cfg(feature = "json")]
+    pub fn to_json(&self) -> Result<String, Error> {
+        Ok(serde_json::to_string_pretty(&UnsignedTransactionJson {
+            summary: self.summary(),
+            body: hex::encode(&self.bytes),
+        })?)
+    }
+
+    /// Load an unsigned transaction from JSON. Only the body is read; the summary is always
+    /// decoded from the body again rather than trusted.
+    #[cfg(feature = "json")]
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        let json: UnsignedTransactionJson = serde_json::from_str(json)?;
+        Self::from_bytes(hex::decode(&json.body)?)
+    }
+}
+
+#[cfg(feature = "json")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct UnsignedTransactionJson {
+    summary: String,
+    body: String,
+}
+
+/// A signature made offline, with the key that made it.
+pub struct DetachedSignature {
+    pub public: PublicKey,
+    pub signature: Signature,
+}
+
+impl DetachedSignature {
+    /// Attach this signature to the transaction it was made for.
+    pub fn attach<T: 'static>(
+        &self,
+        transaction: &mut Transaction<T, TransactionRaw>,
+    ) -> Result<(), Error> {
+        transaction.add_signature(&self.public, &self.signature)?;
+        Ok(())
+    }
+
+    /// The public key followed by the signature, as raw bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.public.as_bytes().to_vec();
+        bytes.extend_from_slice(&self.signature.to_bytes()[..]);
+        bytes
+    }
+
+    pub fn from_bytes(bytes: impl AsRef<[u8]>) -> Result<Self, Error> {
+        let bytes = bytes.as_ref();
+
+        if bytes.len() != ed25519_dalek::PUBLIC_KEY_LENGTH + ed25519_dalek::SIGNATURE_LENGTH {
+            Err(ErrorKind::Parse("{public key}{signature}"))?;
+        }
+
+        let (public, signature) = bytes.split_at(ed25519_dalek::PUBLIC_KEY_LENGTH);
+
+        Ok(Self {
+            public: PublicKey::from_bytes(public)?,
+            signature: Signature::from_bytes(signature)?,
+        })
+    }
+}
+
+impl fmt::Display for DetachedSignature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&hex::encode(self.to_bytes()))
+    }
+}
+
+impl FromStr for DetachedSignature {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_bytes(hex::decode(s)?)
+    }
+}
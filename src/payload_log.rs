@@ -0,0 +1,109 @@
+use crate::{proto, AccountId, TransactionId};
+use protobuf::Message;
+
+/// How much of each request and response a client logs at the `trace` level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PayloadLogging {
+    /// The whole protobuf message. This includes memos, keys and file contents, and can be
+    /// very large.
+    Full,
+
+    /// Only the kind of the message, its size in bytes and the transaction it is for.
+    Summary,
+}
+
+impl Default for PayloadLogging {
+    fn default() -> Self {
+        PayloadLogging::Full
+    }
+}
+
+// Log a request of kind `kind` sent to `node`, for the transaction `id` (the payment of a
+// query, if it has one)
+pub(crate) fn sent(
+    mode: PayloadLogging,
+    target: &str,
+    node: AccountId,
+    kind: &str,
+    id: Option<&TransactionId>,
+    message: &impl Message,
+) {
+    log(mode, target, "sent to", node, kind, id, message);
+}
+
+// Log the response from `node` to a request of kind `kind`
+pub(crate) fn received(
+    mode: PayloadLogging,
+    target: &str,
+    node: AccountId,
+    kind: &str,
+    id: Option<&TransactionId>,
+    message: &impl Message,
+) {
+    log(mode, target, "recv from", node, kind, id, message);
+}
+
+fn log(
+    mode: PayloadLogging,
+    target: &str,
+    direction: &str,
+    node: AccountId,
+    kind: &str,
+    id: Option<&TransactionId>,
+    message: &impl Message,
+) {
+    // computing the size of a message walks all of it
+    if !log::log_enabled!(target: target, log::Level::Trace) {
+        return;
+    }
+
+    match (mode, id) {
+        (PayloadLogging::Full, _) => {
+            log::trace!(target: target, "{} {}: {:#?}", direction, node, message);
+        }
+
+        (PayloadLogging::Summary, Some(id)) => log::trace!(
+            target: target,
+            "{} {}: {} ({} bytes) for {}",
+            direction,
+            node,
+            kind,
+            message.compute_size(),
+            id
+        ),
+
+        (PayloadLogging::Summary, None) => log::trace!(
+            target: target,
+            "{} {}: {} ({} bytes)",
+            direction,
+            node,
+            kind,
+            message.compute_size()
+        ),
+    }
+}
+
+// The name of the kind of query, as it is called in the HAPI protobufs
+pub(crate) fn query_kind_name(query: &proto::Query::Query) -> &'static str {
+    use crate::proto::Query::Query_oneof_query::*;
+
+    match query.query {
+        Some(getByKey(_)) => "GetByKey",
+        Some(getBySolidityID(_)) => "GetBySolidityID",
+        Some(contractCallLocal(_)) => "ContractCallLocal",
+        Some(contractGetInfo(_)) => "ContractGetInfo",
+        Some(contractGetBytecode(_)) => "ContractGetBytecode",
+        Some(ContractGetRecords(_)) => "ContractGetRecords",
+        Some(cryptogetAccountBalance(_)) => "CryptoGetAccountBalance",
+        Some(cryptoGetAccountRecords(_)) => "CryptoGetAccountRecords",
+        Some(cryptoGetInfo(_)) => "CryptoGetInfo",
+        Some(cryptoGetClaim(_)) => "CryptoGetClaim",
+        Some(cryptoGetProxyStakers(_)) => "CryptoGetProxyStakers",
+        Some(fileGetContents(_)) => "FileGetContents",
+        Some(fileGetInfo(_)) => "FileGetInfo",
+        Some(transactionGetReceipt(_)) => "TransactionGetReceipt",
+        Some(transactionGetRecord(_)) => "TransactionGetRecord",
+        Some(transactionGetFastRecord(_)) => "TransactionGetFastRecord",
+        None => "Empty",
+    }
+}
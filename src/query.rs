@@ -1,3 +1,8 @@
+//! Every query, and [`Query`] that runs them.
+//!
+//! Each query type can be built with a client (`new`, or a method on [`Client`](crate::Client))
+//! or without one (`body`). Query types are kept at this path when their files move.
+
 mod query_contract_get_bytecode;
 mod query_contract_get_info;
 mod query_contract_get_records;
@@ -21,29 +26,36 @@ pub use self::{
 };
 
 use crate::{
+    clock::SharedClock,
+    health::Outcome,
+    network::Network,
+    payload_log,
     proto::{
-        self,
-        CryptoService_grpc::{CryptoService, CryptoServiceClient},
-        FileService_grpc::{FileService, FileServiceClient},
+        self, CryptoService_grpc::CryptoService, FileService_grpc::FileService,
         Query::Query_oneof_query,
-        QueryHeader::QueryHeader,
-        SmartContractService_grpc::{SmartContractService, SmartContractServiceClient},
-        ToProto,
+        QueryHeader::{QueryHeader, ResponseType},
+        SmartContractService_grpc::SmartContractService, ToProto,
     },
+    retry::{self, OnRetry},
+    routing::Routes,
+    trace::{self, ExecutionTrace, TraceStep, Tracer},
     transaction::{Transaction, TransactionCryptoTransfer},
-    AccountId, Client, ErrorKind, SecretKey, Status,
+    AccountId, Budget, Client, ErrorKind, SecretKey, Status, TransactionId,
 };
+use chrono::{DateTime, Utc};
 use failure::Error;
 use futures::compat::Compat01As03;
 use futures::{Future};
+use parking_lot::Mutex;
+use protobuf::{parse_from_bytes, Message};
 use std::{
+    any::Any,
     marker::PhantomData,
     sync::{
         atomic::{AtomicUsize, Ordering},
         Arc,
     },
-    thread::sleep,
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 pub(crate) trait ToQueryProto {
@@ -51,6 +63,30 @@ pub(crate) trait ToQueryProto {
         false
     }
     fn to_query_proto(&self, header: QueryHeader) -> Result<Query_oneof_query, Error>;
+
+    /// Translate an error from sending this query, at `now`, into a more specific one,
+    /// if possible.
+    fn map_error(&self, error: Error, _now: DateTime<Utc>) -> Error {
+        error
+    }
+
+    /// Adjust the response to this query before it is returned, e.g. to filter it.
+    fn map_response(
+        &self,
+        response: proto::Response::Response,
+    ) -> Result<proto::Response::Response, Error> {
+        Ok(response)
+    }
+
+    /// A transaction that must have a receipt before this query is worth paying for.
+    fn receipt_required(&self) -> Option<TransactionId> {
+        None
+    }
+
+    /// The query itself, for setters that only apply to one kind of query.
+    fn as_any_mut(&mut self) -> Option<&mut dyn Any> {
+        None
+    }
 }
 
 #[doc(hidden)]
@@ -68,17 +104,57 @@ impl QueryResponse for () {
     }
 }
 
+/// The header of a response to a query.
+#[derive(Debug, Clone)]
+pub struct ResponseHeader {
+    /// The result of the pre-check of the query (and its payment) by the node.
+    pub status: Status,
+
+    /// The cost of the query in tinybar, if the cost was asked for.
+    pub cost: u64,
+
+    /// The state proof of the answer, if one was asked for and is available.
+    pub state_proof: Option<Vec<u8>>,
+
+    /// The node that answered.
+    pub node: AccountId,
+}
+
+impl ResponseHeader {
+    fn new(mut header: proto::ResponseHeader::ResponseHeader, node: AccountId) -> Self {
+        let state_proof = header.take_stateProof();
+
+        Self {
+            status: header.get_nodeTransactionPrecheckCode().into(),
+            cost: header.get_cost(),
+            state_proof: if state_proof.is_empty() {
+                None
+            } else {
+                Some(state_proof)
+            },
+            node,
+        }
+    }
+}
+
 pub struct Query<T>
 where
     T: QueryResponse + Send + Sync + 'static,
 {
-    crypto_service: Arc<CryptoServiceClient>,
-    contract_service: Arc<SmartContractServiceClient>,
-    file_service: Arc<FileServiceClient>,
+    network: Arc<Network>,
+    routes: Arc<Routes>,
     payment: Option<proto::Transaction::Transaction>,
     secret: Option<Arc<dyn Fn() -> Result<SecretKey, Error> + Send + Sync>>,
     operator: Option<AccountId>,
     node: Option<AccountId>,
+    shard_realm: (i64, i64),
+    on_retry: Option<OnRetry>,
+    tracer: Option<Tracer>,
+    budget: Option<Budget>,
+    clock: SharedClock,
+    response_type: ResponseType,
+    max_cost: Option<u64>,
+    last_header: Option<ResponseHeader>,
     inner: Box<dyn ToQueryProto + Send + Sync>,
     phantom: PhantomData<T>,
 }
@@ -91,29 +167,132 @@ where
     where
         T: ToQueryProto,
     {
+        Self::with_inner(client, Box::new(inner))
+    }
+
+    fn with_inner(client: &Client, inner: Box<dyn ToQueryProto + Send + Sync>) -> Self {
+        // the kind of query decides which service, and so which node, it goes to
+        let node = inner
+            .to_query_proto(QueryHeader::new())
+            .ok()
+            .and_then(|query| client.routes.query_node(&query))
+            .or_else(|| Some(client.default_node()));
+
         Self {
             payment: None,
-            crypto_service: client.crypto.clone(),
-            contract_service: client.contract.clone(),
-            file_service: client.file.clone(),
-            node: client.node,
+            network: client.network.clone(),
+            routes: client.routes.clone(),
+            node,
+            shard_realm: (client.shard, client.realm),
             operator: client.operator,
             secret: client.operator_secret.clone(),
-            inner: Box::new(inner),
+            on_retry: None,
+            tracer: None,
+            budget: client.budget.clone(),
+            clock: client.clock.clone(),
+            response_type: ResponseType::ANSWER_ONLY,
+            max_cost: None,
+            last_header: None,
+            inner,
             phantom: PhantomData,
         }
     }
 
+    pub(crate) fn inner(&mut self) -> &mut T {
+        self.inner
+            .as_any_mut()
+            .and_then(|inner| inner.downcast_mut())
+            .expect("query does not expose its inner state")
+    }
+
+    /// Pay for this query with `transaction` instead of a payment generated from the
+    /// operator. Fails for free queries (e.g. for receipts), which are never paid for.
     pub fn payment<S: 'static>(
         &mut self,
         transaction: &mut Transaction<TransactionCryptoTransfer, S>,
     ) -> Result<&mut Self, Error> {
+        if self.inner.is_free() {
+            Err(ErrorKind::UnexpectedPayment)?;
+        }
+
         self.payment = Some(transaction.build().take_raw()?.tx);
         Ok(self)
     }
 
+    /// Sign the automatically generated payment for this query with `secret` instead of
+    /// the operator secret of the client.
+    ///
+    /// Has no effect if a payment is given with `payment`.
+    pub fn payment_signer(&mut self, secret: SecretKey) -> &mut Self {
+        self.secret = Some(Arc::new(move || Ok(secret.clone())));
+        self
+    }
+
+    /// Call `callback` each time this query is retried after a `Busy` response, with the
+    /// attempt number, the response status and the delay before the next attempt.
+    pub fn on_retry(
+        &mut self,
+        callback: impl Fn(usize, Status, Duration) + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.on_retry = Some(Arc::new(callback));
+        self
+    }
+
+    /// Count the attempts and payment of this query against `budget`, instead of any
+    /// budget of the client.
+    pub fn budget(&mut self, budget: &Budget) -> &mut Self {
+        self.budget = Some(budget.clone());
+        self
+    }
+
+    /// Refuse to run the query if the network quotes a cost above `cost` tinybar.
+    ///
+    /// The cost is asked for before each execution of the query.
+    pub fn max_cost(&mut self, cost: u64) -> &mut Self {
+        self.max_cost = Some(cost);
+        self
+    }
+
+    /// Ask the network what this query would cost to run, without running it.
+    pub async fn cost_async(&mut self) -> Result<u64, Error> {
+        if self.inner.is_free() {
+            return Ok(0);
+        }
+
+        let given_payment = self.payment.is_some();
+
+        self.response_type = ResponseType::COST_ANSWER;
+        let response = self.send().await;
+        self.response_type = ResponseType::ANSWER_ONLY;
+
+        // with strict payments, the payment made for asking the cost must not go on to pay
+        // for the answer
+        if self.network.strict_payments && !given_payment {
+            self.payment = None;
+        }
+
+        let header = response?.0;
+        let cost = header.cost;
+        self.last_header = Some(header);
+
+        Ok(cost)
+    }
+
+    /// The header of the last successful response to this query, including cost queries.
+    ///
+    /// `None` until the query has been run.
+    pub fn last_header(&self) -> Option<&ResponseHeader> {
+        self.last_header.as_ref()
+    }
+
+    pub fn cost(&mut self) -> Result<u64, Error> {
+        crate::RUNTIME
+            .lock()
+            .block_on(self.cost_async())
+    }
+
     pub async fn get_async(&mut self) -> Result<T::Response, Error> {
-        T::get(self.send().await?.1)
+        T::get(self.execute().await?)
     }
 
     pub fn get(&mut self) -> Result<T::Response, Error> {
@@ -122,57 +301,201 @@ where
             .block_on(self.get_async())
     }
 
+    /// Run the query, recording when each step happened.
+    pub async fn get_traced_async(&mut self) -> (Result<T::Response, Error>, ExecutionTrace) {
+        let tracer = Arc::new(Mutex::new(ExecutionTrace::new()));
+
+        self.tracer = Some(tracer.clone());
+        let result = self.get_async().await;
+        self.tracer = None;
+
+        let trace = tracer.lock().clone();
+
+        (result, trace)
+    }
+
+    pub fn get_traced(&mut self) -> (Result<T::Response, Error>, ExecutionTrace) {
+        crate::RUNTIME
+            .lock()
+            .block_on(self.get_traced_async())
+    }
+
+    async fn execute(&mut self) -> Result<proto::Response::Response, Error> {
+        if let Some(id) = self.inner.receipt_required() {
+            self.check_receipt(id).await?;
+        }
+
+        if self.network.strict_payments && !self.inner.is_free() && self.payment.is_none() {
+            let cost = self.cost_async().await?;
+            Err(ErrorKind::PaymentRequired { cost })?;
+        }
+
+        if let Some(max) = self.max_cost {
+            let cost = self.cost_async().await?;
+
+            if cost > max {
+                Err(ErrorKind::QueryCostExceeded { cost, max })?;
+            }
+        }
+
+        match self.send().await {
+            Ok((header, response)) => {
+                self.last_header = Some(header);
+                self.inner.map_response(response)
+            }
+
+            Err(error) => Err(self.inner.map_error(error, self.clock.now())),
+        }
+    }
+
+    // Fail without paying for the query if the network does not have a receipt for `id` yet
+    async fn check_receipt(&self, id: TransactionId) -> Result<(), Error> {
+        let not_generated = match QueryTransactionGetReceipt::new(&self.client(), id)
+            .get_async()
+            .await
+        {
+            Ok(receipt) => receipt.status == Status::Unknown,
+
+            Err(error) => match error.downcast_ref() {
+                Some(ErrorKind::PreCheck(Status::ReceiptNotFound)) => true,
+
+                // the receipt is gone, but the record is kept for longer
+                Some(ErrorKind::ReceiptExpired(_)) => false,
+
+                _ => return Err(error),
+            },
+        };
+
+        if not_generated {
+            Err(ErrorKind::RecordNotGenerated(id))?;
+        }
+
+        Ok(())
+    }
+
+    // A client with the settings of this query, for the requests it makes on its own
+    fn client(&self) -> Client {
+        Client {
+            node: self.node,
+            routes: self.routes.clone(),
+            operator: self.operator,
+            operator_secret: self.secret.clone(),
+            operators: Arc::default(),
+            fee_policy: None,
+            budget: self.budget.clone(),
+            shard: self.shard_realm.0,
+            realm: self.shard_realm.1,
+            clock: self.clock.clone(),
+            network: self.network.clone(),
+        }
+    }
+
+    // A payment from the operator for this query, sent to the node the query goes to;
+    // without the node set on it, the payment would be routed as a transaction
+    fn implicit_payment(&self) -> Option<proto::Transaction::Transaction> {
+        let node = self.node?;
+        let operator = self.operator?;
+        let cost = 100_300_000;
+
+        TransactionCryptoTransfer::new(&self.client())
+            .node(node)
+            .transfer(node, cost)
+            .transfer(operator, -cost)
+            .build()
+            .take_raw()
+            .ok()
+            .map(|tx| tx.tx)
+    }
+
     fn send(
         &mut self,
-    ) -> impl Future<
-        Output = Result<
-            (
-                proto::ResponseHeader::ResponseHeader,
-                proto::Response::Response,
-            ),
-            Error,
-        >,
-    > {
+    ) -> impl Future<Output = Result<(ResponseHeader, proto::Response::Response), Error>> {
         use self::proto::Query::Query_oneof_query::*;
 
-        if !self.inner.is_free() && self.payment.is_none() {
+        // with strict payments, only cost queries (which are not charged) get a payment
+        let implicit_payment =
+            !self.network.strict_payments || self.response_type == ResponseType::COST_ANSWER;
+
+        if !self.inner.is_free() && self.payment.is_none() && implicit_payment {
             // Attach a payment transaction if this is a non-free query and we
             // have payment details
             if self.operator.is_some() && self.node.is_some() && self.secret.is_some() {
-                let cost = 100_300_000;
-                self.payment = TransactionCryptoTransfer::new(&Client {
-                    node: self.node.clone(),
-                    operator: self.operator.clone(),
-                    operator_secret: self.secret.clone(),
-                    crypto: self.crypto_service.clone(),
-                    file: self.file_service.clone(),
-                    contract: self.contract_service.clone(),
-                })
-                .transfer(*self.node.as_ref().unwrap(), cost as i64)
-                .transfer(*self.operator.as_ref().unwrap(), -(cost as i64))
-                .build()
-                .take_raw()
-                .ok()
-                .map(|tx| tx.tx);
+                self.payment = self.implicit_payment();
+
+                trace::record(&self.tracer, TraceStep::Build);
             }
         }
 
         let attempt = AtomicUsize::new(0);
-        let crypto = self.crypto_service.clone();
-        let file = self.file_service.clone();
-        let contract = self.contract_service.clone();
+        let network = self.network.clone();
+        let node = self.node.unwrap_or_else(|| network.default_node());
+        let on_retry = self.on_retry.clone();
+        let tracer = self.tracer.clone();
+        let budget = self.budget.clone();
+        let clock = self.clock.clone();
         let query_res: Option<Result<proto::Query::Query, _>> = Some(self.to_proto());
 
+        // the query commits to paying what its payment transfers to the node
+        #[allow(clippy::cast_sign_loss)]
+        let payment = self.payment.as_ref().map_or(0, |payment| {
+            payment
+                .get_body()
+                .get_cryptoTransfer()
+                .get_transfers()
+                .get_accountAmounts()
+                .iter()
+                .map(|amount| amount.get_amount())
+                .filter(|amount| *amount > 0)
+                .sum::<i64>() as u64
+        });
+
+        let payment_id: Option<TransactionId> = self
+            .payment
+            .as_ref()
+            .map(|payment| payment.get_body().get_transactionID().clone().into());
+
         async move {
+            if let Some(budget) = &budget {
+                budget.spend_fee(payment)?;
+            }
+
+            let _request = network.start_request()?;
+            let mut channel = network.channel(Some(node))?;
+            let mut reconnects = 0;
+
             #[allow(clippy::never_loop)]
             loop {
                 break if let Some(Ok(query)) = &query_res {
+                    let kind = payload_log::query_kind_name(query);
+
                     if attempt.load(Ordering::SeqCst) == 0 {
-                        log::trace!("sent: {:#?}", query);
+                        payload_log::sent(
+                            network.payload_logging,
+                            "hedera::query",
+                            node,
+                            kind,
+                            payment_id.as_ref(),
+                            query,
+                        );
                     }
 
+                    let submit = TraceStep::Submit {
+                        attempt: attempt.load(Ordering::SeqCst),
+                        node,
+                    };
+
+                    if let Some(budget) = &budget {
+                        budget.spend_attempt()?;
+                    }
+
+                    trace::record(&tracer, submit);
+
                     let query = query.clone();
+                    let (crypto, file, contract) =
+                        (&channel.crypto, &channel.file, &channel.contract);
+
                     let o = grpc::RequestOptions::default();
+                    let sent = Instant::now();
                     let response = match query.query {
                         //////////////////////// CRYPTO QUERIES
                         Some(cryptogetAccountBalance(_)) => crypto.crypto_get_balance(o, query),
@@ -188,22 +511,108 @@ where
                         Some(contractGetInfo(_)) => contract.get_contract_info(o, query),
                         Some(contractGetBytecode(_)) => contract.contract_get_bytecode(o, query),
                         Some(contractCallLocal(_)) => contract.contract_call_local_method(o, query),
+                        Some(ContractGetRecords(_)) => {
+                            contract.get_tx_record_by_contract_id(o, query)
+                        }
+                        Some(getBySolidityID(_)) => contract.get_by_solidity_id(o, query),
+                        //////////////////////// OTHER CRYPTO QUERIES
+                        Some(cryptoGetClaim(_)) => crypto.get_claim(o, query),
+                        Some(cryptoGetProxyStakers(_)) => {
+                            crypto.get_stakers_by_account_id(o, query)
+                        }
+                        Some(transactionGetFastRecord(_)) => {
+                            crypto.get_fast_transaction_record(o, query)
+                        }
+
+                        // no service of the network answers these
+                        Some(getByKey(_)) | None => Err(ErrorKind::UnsupportedQueryKind(kind))?,
+                    };
+
+                    let mut response = match Compat01As03::new(response.drop_metadata()).await {
+                        Ok(response) => response,
+
+                        Err(ref error)
+                            if retry::is_connection_error(error)
+                                && reconnects < retry::MAX_RECONNECTS =>
+                        {
+                            network.record(Some(node), Outcome::Failed, sent.elapsed());
+                            reconnects += 1;
+                            trace::record(&tracer, TraceStep::Reconnect);
+                            channel = network.reconnect(Some(node), &channel)?;
+                            continue;
+                        }
+
+                        Err(error) => {
+                            log::warn!(
+                                target: "hedera::query",
+                                "{} did not respond: {}",
+                                node,
+                                error
+                            );
+                            network.record(Some(node), Outcome::Failed, sent.elapsed());
+                            Err(retry::transport_error(error))?
+                        }
+                    };
+
+                    payload_log::received(
+                        network.payload_logging,
+                        "hedera::query",
+                        node,
+                        kind,
+                        payment_id.as_ref(),
+                        &response,
+                    );
+
+                    let header = take_header(&mut response)?;
+                    let status = header.get_nodeTransactionPrecheckCode().into();
+                    trace::record(&tracer, TraceStep::Response { status });
 
-                        _ => unreachable!(),
+                    let outcome = match status {
+                        Status::Busy => Outcome::Busy,
+                        _ => Outcome::Response,
                     };
 
-                    let mut response = Compat01As03::new(response.drop_metadata()).await?;
-                    log::trace!("recv: {:#?}", response);
+                    network.record(Some(node), outcome, sent.elapsed());
+                    record_stats(&network, status, payment, &response);
+
+                    match status {
+                        Status::Busy if attempt.load(Ordering::SeqCst) < retry::MAX_ATTEMPTS => {
+                            network.stats.lock().record_retry();
 
-                    let header = take_header(&mut response);
-                    match header.get_nodeTransactionPrecheckCode().into() {
-                        Status::Busy if attempt.load(Ordering::SeqCst) < 5 => {
                             let attempt = attempt.fetch_add(1, Ordering::SeqCst) + 1;
-                            sleep(Duration::from_secs((attempt * 2) as u64));
+                            let delay = retry::backoff(attempt);
+
+                            log::debug!(
+                                target: "hedera::query",
+                                "{} is busy; retrying in {:?}",
+                                node,
+                                delay
+                            );
+
+                            trace::record(
+                                &tracer,
+                                TraceStep::Retry {
+                                    attempt,
+                                    status,
+                                    delay,
+                                },
+                            );
+
+                            if let Some(on_retry) = &on_retry {
+                                on_retry(attempt, Status::Busy, delay);
+                            }
+
+                            clock.sleep(delay).await;
                             continue;
                         }
 
-                        Status::Ok => Ok((header, response)),
+                        Status::Ok => Ok((ResponseHeader::new(header, node), response)),
+
+                        // nodes leave the cost at 0 unless they worked it out
+                        Status::InsufficientTxFee => Err(ErrorKind::InsufficientFee {
+                            offered: payment,
+                            required: Some(header.get_cost()).filter(|cost| *cost > 0),
+                        })?,
 
                         pre_check_code => Err(ErrorKind::PreCheck(pre_check_code))?,
                     }
@@ -217,6 +626,75 @@ where
     }
 }
 
+#[cfg(feature = "json")]
+impl<T> Query<T>
+where
+    T: QueryResponse + Send + Sync + 'static,
+    T::Response: crate::export::ToJson,
+{
+    /// Run the query and convert the response to JSON, for scripting layers that
+    /// do not bind each response type.
+    pub async fn get_json_async(&mut self) -> Result<serde_json::Value, Error> {
+        use crate::export::ToJson;
+
+        Ok(self.get_async().await?.to_json())
+    }
+
+    pub fn get_json(&mut self) -> Result<serde_json::Value, Error> {
+        crate::RUNTIME
+            .lock()
+            .block_on(self.get_json_async())
+    }
+}
+
+/// A query built without a client, e.g. ahead of time or in a test, to be run later
+/// with `Client::execute_query`.
+pub struct QueryBody<T> {
+    inner: Box<dyn ToQueryProto + Send + Sync>,
+    phantom: PhantomData<T>,
+}
+
+impl<T> QueryBody<T>
+where
+    T: QueryResponse + Send + Sync + 'static,
+{
+    pub(crate) fn new(inner: T) -> Self
+    where
+        T: ToQueryProto,
+    {
+        Self {
+            inner: Box::new(inner),
+            phantom: PhantomData,
+        }
+    }
+
+    pub(crate) fn bind(self, client: &Client) -> Query<T> {
+        Query::with_inner(client, self.inner)
+    }
+
+    /// The serialized protobuf `Query`, without a payment.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut query = proto::Query::Query::new();
+        query.query = Some(self.inner.to_query_proto(QueryHeader::new())?);
+
+        Ok(query.write_to_bytes()?)
+    }
+
+    /// Parse a serialized protobuf `Response` to this query.
+    pub fn parse_response(&self, bytes: impl AsRef<[u8]>) -> Result<T::Response, Error> {
+        let mut response: proto::Response::Response = parse_from_bytes(bytes.as_ref())?;
+
+        match take_header(&mut response)?.get_nodeTransactionPrecheckCode().into() {
+            Status::Ok => T::get(self.inner.map_response(response)?),
+            status => {
+                // a query body has no client, and so no clock of its own
+                let error = ErrorKind::PreCheck(status).into();
+                Err(self.inner.map_error(error, Utc::now()))
+            }
+        }
+    }
+}
+
 impl<T> ToProto<proto::Query::Query> for Query<T>
 where
     T: QueryResponse + Send + Sync + 'static,
@@ -224,12 +702,15 @@ where
     fn to_proto(&self) -> Result<proto::Query::Query, Error> {
         let mut header = proto::QueryHeader::QueryHeader::new();
 
-        header.set_responseType(proto::QueryHeader::ResponseType::ANSWER_ONLY);
+        header.set_responseType(self.response_type);
 
-        if let Some(payment) = &self.payment {
-            header.set_payment(payment.clone());
-        } else if !self.inner.is_free() {
-            return Err(ErrorKind::MissingField("payment"))?;
+        // free queries are sent without a payment even if one was somehow attached, so that
+        // the node reports the actual problem with the query rather than charging for it
+        if !self.inner.is_free() {
+            match &self.payment {
+                Some(payment) => header.set_payment(payment.clone()),
+                None => return Err(ErrorKind::MissingField("payment"))?,
+            }
         }
 
         let mut query = proto::Query::Query::new();
@@ -239,14 +720,36 @@ where
     }
 }
 
+// Count the pre-check `status` of a response, the `payment` of an answered query, and the
+// status of a receipt in the response
+fn record_stats(
+    network: &Network,
+    status: Status,
+    payment: u64,
+    response: &proto::Response::Response,
+) {
+    use self::proto::Response::Response_oneof_response::transactionGetReceipt;
+
+    let mut stats = network.stats.lock();
+    stats.record_precheck(status);
+
+    if status == Status::Ok {
+        stats.record_fee(payment);
+
+        if let Some(transactionGetReceipt(response)) = &response.response {
+            stats.record_receipt(response.get_receipt().get_status().into());
+        }
+    }
+}
+
 // this is needed because some times a query is responded to with the wrong
 // envelope type when an error occurs; this ensures we can get the error
 pub(crate) fn take_header(
     response: &mut proto::Response::Response,
-) -> proto::ResponseHeader::ResponseHeader {
+) -> Result<proto::ResponseHeader::ResponseHeader, Error> {
     use self::proto::Response::Response_oneof_response::*;
 
-    match &mut response.response {
+    Ok(match &mut response.response {
         Some(getByKey(ref mut res)) => res.take_header(),
         Some(getBySolidityID(ref mut res)) => res.take_header(),
         Some(contractCallLocal(ref mut res)) => res.take_header(),
@@ -264,6 +767,45 @@ pub(crate) fn take_header(
         Some(transactionGetRecord(ref mut res)) => res.take_header(),
         Some(transactionGetFastRecord(ref mut res)) => res.take_header(),
 
-        None => unreachable!(),
+        None => Err(ErrorKind::MissingField("response"))?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{query::QueryCryptoGetInfo, AccountId, Client, SecretKey};
+    use failure::Error;
+
+    #[test]
+    fn test_implicit_payment_goes_to_query_node() -> Result<(), Error> {
+        let (secret, _) = SecretKey::generate("");
+        let query_node = AccountId::new(0, 0, 3);
+        let transaction_node = AccountId::new(0, 0, 4);
+
+        let client = Client::builder()
+            .node("0.0.3@127.0.0.1:50211".parse()?)
+            .node("0.0.4@127.0.0.1:50212".parse()?)
+            .query_node(query_node)
+            .transaction_node(transaction_node)
+            .operator(AccountId::new(0, 0, 1001), move || secret.clone())
+            .build()?;
+
+        let query = client.account(AccountId::new(0, 0, 1002)).info();
+        let payment = query.implicit_payment().unwrap();
+
+        assert_eq!(
+            AccountId::from(payment.get_body().get_nodeAccountID().clone()),
+            query_node
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_empty_response() {
+        let body = QueryCryptoGetInfo::body(AccountId::new(0, 0, 1001));
+
+        // an empty message is a valid `Response`, but one without a response in it
+        assert!(body.parse_response(b"").is_err());
     }
 }
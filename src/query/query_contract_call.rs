@@ -1,32 +1,53 @@
 use crate::{
     proto::{self, Query::Query_oneof_query, QueryHeader::QueryHeader, ToProto},
-    query::{Query, QueryResponse, ToQueryProto},
-    Client, ContractId, function_result::ContractFunctionResult
+    query::{Query, QueryBody, QueryResponse, ToQueryProto},
+    function_result::ContractFunctionResult,
+    Client, ContractId,
 };
 use failure::Error;
 
-pub struct QueryContractCall {
+/// Run a function of a contract on one node without changing its state, e.g. to read
+/// from the contract without submitting a transaction.
+pub struct QueryContractCallLocal {
     contract_id: ContractId,
     gas: i64,
     function_parameters: Vec<u8>,
-    max_result_size: i64
+    max_result_size: i64,
 }
 
-impl QueryContractCall {
-    pub fn new(client: &Client, contract_id: ContractId, gas: i64,
-    function_parameters: Vec<u8>, max_result_size: i64) -> Query<Self> {
-        Query::new(
-            client,
-            Self {
+/// The former name of `QueryContractCallLocal`.
+#[deprecated(note = "renamed to `QueryContractCallLocal`")]
+pub type QueryContractCall = QueryContractCallLocal;
+
+impl QueryContractCallLocal {
+    /// `function_parameters` selects the function and holds its encoded parameters; results
+    /// longer than `max_result_size` bytes are refused by the node.
+    pub fn new(
+        client: &Client,
+        contract_id: ContractId,
+        gas: i64,
+        function_parameters: Vec<u8>,
+        max_result_size: i64,
+    ) -> Query<Self> {
+        Self::body(contract_id, gas, function_parameters, max_result_size).bind(client)
+    }
+
+    pub fn body(
+        contract_id: ContractId,
+        gas: i64,
+        function_parameters: Vec<u8>,
+        max_result_size: i64,
+    ) -> QueryBody<Self> {
+        QueryBody::new(Self {
             contract_id,
             gas,
             function_parameters,
-            max_result_size
+            max_result_size,
         })
     }
 }
 
-impl QueryResponse for QueryContractCall {
+impl QueryResponse for QueryContractCallLocal {
     type Response = ContractFunctionResult;
 
     fn get(mut response: proto::Response::Response) -> Result<Self::Response, Error> {
@@ -34,7 +55,7 @@ impl QueryResponse for QueryContractCall {
     }
 }
 
-impl ToQueryProto for QueryContractCall {
+impl ToQueryProto for QueryContractCallLocal {
     fn to_query_proto(&self, header: QueryHeader) -> Result<Query_oneof_query, Error> {
         let mut query = proto::ContractCallLocal::ContractCallLocalQuery::new();
         query.set_header(header);
@@ -45,4 +66,4 @@ impl ToQueryProto for QueryContractCall {
 
         Ok(Query_oneof_query::contractCallLocal(query))
     }
-}
\ No newline at end of file
+}
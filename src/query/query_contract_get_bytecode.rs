@@ -1,9 +1,10 @@
 use crate::{
     proto::{self, Query::Query_oneof_query, QueryHeader::QueryHeader, ToProto},
-    query::{Query, QueryResponse, ToQueryProto},
+    query::{Query, QueryBody, QueryResponse, ToQueryProto},
     Client, ContractId,
 };
 use failure::Error;
+use std::io::Write;
 
 pub struct QueryContractGetBytecode {
     contract_id: ContractId,
@@ -11,7 +12,30 @@ pub struct QueryContractGetBytecode {
 
 impl QueryContractGetBytecode {
     pub fn new(client: &Client, contract_id: ContractId) -> Query<Self> {
-        Query::new(client, Self { contract_id })
+        Self::body(contract_id).bind(client)
+    }
+
+    pub fn body(contract_id: ContractId) -> QueryBody<Self> {
+        QueryBody::new(Self { contract_id })
+    }
+}
+
+impl Query<QueryContractGetBytecode> {
+    /// Write the bytecode of the contract to `writer` instead of returning it,
+    /// returning the number of bytes written.
+    pub fn write_to(&mut self, writer: impl Write) -> Result<usize, Error> {
+        crate::RUNTIME
+            .lock()
+            .block_on(self.write_to_async(writer))
+    }
+
+    pub async fn write_to_async(&mut self, mut writer: impl Write) -> Result<usize, Error> {
+        let response = self.execute().await?;
+        let bytecode = response.get_contractGetBytecodeResponse().get_bytecode();
+
+        writer.write_all(bytecode)?;
+
+        Ok(bytecode.len())
     }
 }
 
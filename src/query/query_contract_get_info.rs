@@ -1,6 +1,6 @@
 use crate::{
     proto::{self, Query::Query_oneof_query, QueryHeader::QueryHeader, ToProto},
-    query::{Query, QueryResponse, ToQueryProto},
+    query::{Query, QueryBody, QueryResponse, ToQueryProto},
     Client, ContractId, ContractInfo,
 };
 use failure::Error;
@@ -12,7 +12,11 @@ pub struct QueryContractGetInfo {
 
 impl QueryContractGetInfo {
     pub fn new(client: &Client, contract: ContractId) -> Query<Self> {
-        Query::new(client, Self { contract })
+        Self::body(contract).bind(client)
+    }
+
+    pub fn body(contract: ContractId) -> QueryBody<Self> {
+        QueryBody::new(Self { contract })
     }
 }
 
@@ -1,6 +1,6 @@
 use crate::{
     proto::{self, Query::Query_oneof_query, QueryHeader::QueryHeader, ToProto},
-    query::{Query, QueryResponse, ToQueryProto},
+    query::{Query, QueryBody, QueryResponse, ToQueryProto},
     AccountId, Client,
 };
 use failure::Error;
@@ -11,7 +11,11 @@ pub struct QueryCryptoGetAccountBalance {
 
 impl QueryCryptoGetAccountBalance {
     pub fn new(client: &Client, account: AccountId) -> Query<Self> {
-        Query::new(client, Self { account })
+        Self::body(account).bind(client)
+    }
+
+    pub fn body(account: AccountId) -> QueryBody<Self> {
+        QueryBody::new(Self { account })
     }
 }
 
@@ -32,3 +36,41 @@ impl ToQueryProto for QueryCryptoGetAccountBalance {
         Ok(Query_oneof_query::cryptogetAccountBalance(query))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::QueryCryptoGetAccountBalance;
+    use crate::{proto, AccountId, ErrorKind, Status};
+    use failure::Error;
+    use protobuf::{parse_from_bytes, Message};
+
+    #[test]
+    fn test_query_body() -> Result<(), Error> {
+        let account = AccountId::new(0, 0, 1001);
+        let body = QueryCryptoGetAccountBalance::body(account);
+
+        let query: proto::Query::Query = parse_from_bytes(&body.to_bytes()?)?;
+        let query = query.get_cryptogetAccountBalance();
+        assert_eq!(AccountId::from(query.get_accountID().clone()), account);
+        assert!(!query.get_header().has_payment());
+
+        let mut response = proto::Response::Response::new();
+        response.mut_cryptogetAccountBalance().set_balance(250);
+        assert_eq!(body.parse_response(response.write_to_bytes()?)?, 250);
+
+        response
+            .mut_cryptogetAccountBalance()
+            .mut_header()
+            .set_nodeTransactionPrecheckCode(proto::ResponseCode::ResponseCodeEnum::BUSY);
+
+        match body.parse_response(response.write_to_bytes()?) {
+            Err(error) => match error.downcast_ref() {
+                Some(ErrorKind::PreCheck(Status::Busy)) => {}
+                _ => panic!("unexpected error: {}", error),
+            },
+            Ok(_) => panic!("expected a pre-check error"),
+        }
+
+        Ok(())
+    }
+}
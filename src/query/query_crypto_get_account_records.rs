@@ -1,19 +1,51 @@
 use crate::{
     id::AccountId,
     proto::{self, Query::Query_oneof_query, QueryHeader::QueryHeader, ToProto},
-    query::{Query, QueryResponse, ToQueryProto},
+    query::{Query, QueryBody, QueryResponse, ToQueryProto},
     Client, TransactionRecord,
 };
+use chrono::{DateTime, Utc};
 use failure::Error;
+use protobuf::RepeatedField;
+use std::any::Any;
 use try_from::TryInto;
 
 pub struct QueryCryptoGetAccountRecords {
     account: AccountId,
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
 }
 
 impl QueryCryptoGetAccountRecords {
     pub fn new(client: &Client, account: AccountId) -> Query<Self> {
-        Query::new(client, Self { account })
+        Self::body(account).bind(client)
+    }
+
+    pub fn body(account: AccountId) -> QueryBody<Self> {
+        QueryBody::new(Self {
+            account,
+            since: None,
+            until: None,
+        })
+    }
+
+    fn includes(&self, time: DateTime<Utc>) -> bool {
+        self.since.map_or(true, |since| time >= since)
+            && self.until.map_or(true, |until| time < until)
+    }
+}
+
+impl Query<QueryCryptoGetAccountRecords> {
+    /// Only return records that reached consensus at or after `time`.
+    pub fn since(&mut self, time: DateTime<Utc>) -> &mut Self {
+        self.inner().since = Some(time);
+        self
+    }
+
+    /// Only return records that reached consensus before `time`.
+    pub fn until(&mut self, time: DateTime<Utc>) -> &mut Self {
+        self.inner().until = Some(time);
+        self
     }
 }
 
@@ -38,4 +70,31 @@ impl ToQueryProto for QueryCryptoGetAccountRecords {
 
         Ok(Query_oneof_query::cryptoGetAccountRecords(query))
     }
+
+    fn map_response(
+        &self,
+        mut response: proto::Response::Response,
+    ) -> Result<proto::Response::Response, Error> {
+        // the network has no way to filter records by time; do it here instead
+        let records = response.mut_cryptoGetAccountRecords();
+        let consensus_at =
+            |record: &proto::TransactionRecord::TransactionRecord| -> DateTime<Utc> {
+                record.get_consensusTimestamp().clone().into()
+            };
+
+        let mut filtered: Vec<_> = records
+            .take_records()
+            .into_iter()
+            .filter(|record| self.includes(consensus_at(record)))
+            .collect();
+
+        filtered.sort_by_key(consensus_at);
+        records.set_records(RepeatedField::from_vec(filtered));
+
+        Ok(response)
+    }
+
+    fn as_any_mut(&mut self) -> Option<&mut dyn Any> {
+        Some(self)
+    }
 }
@@ -1,7 +1,7 @@
 use crate::{
     claim::Claim,
     proto::{self, Query::Query_oneof_query, QueryHeader::QueryHeader, ToProto},
-    query::{Query, QueryResponse, ToQueryProto},
+    query::{Query, QueryBody, QueryResponse, ToQueryProto},
     AccountId, Client,
 };
 use failure::Error;
@@ -14,7 +14,11 @@ pub struct QueryCryptoGetClaim {
 
 impl QueryCryptoGetClaim {
     pub fn new(client: &Client, account: AccountId, hash: Vec<u8>) -> Query<Self> {
-        Query::new(client, Self { account, hash })
+        Self::body(account, hash).bind(client)
+    }
+
+    pub fn body(account: AccountId, hash: Vec<u8>) -> QueryBody<Self> {
+        QueryBody::new(Self { account, hash })
     }
 }
 
@@ -1,6 +1,6 @@
 use crate::{
     proto::{self, Query::Query_oneof_query, QueryHeader::QueryHeader, ToProto},
-    query::{Query, QueryResponse, ToQueryProto},
+    query::{Query, QueryBody, QueryResponse, ToQueryProto},
     AccountId, AccountInfo, Client,
 };
 use failure::Error;
@@ -12,7 +12,11 @@ pub struct QueryCryptoGetInfo {
 
 impl QueryCryptoGetInfo {
     pub fn new(client: &Client, account: AccountId) -> Query<Self> {
-        Query::new(client, Self { account })
+        Self::body(account).bind(client)
+    }
+
+    pub fn body(account: AccountId) -> QueryBody<Self> {
+        QueryBody::new(Self { account })
     }
 }
 
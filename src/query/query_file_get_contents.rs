@@ -1,7 +1,7 @@
 use crate::{
     id::FileId,
     proto::{self, Query::Query_oneof_query, QueryHeader::QueryHeader, ToProto},
-    query::{Query, QueryResponse, ToQueryProto},
+    query::{Query, QueryBody, QueryResponse, ToQueryProto},
     Client,
 };
 use failure::Error;
@@ -23,7 +23,11 @@ pub struct QueryFileGetContents {
 
 impl QueryFileGetContents {
     pub fn new(client: &Client, file: FileId) -> Query<Self> {
-        Query::new(client, Self { file })
+        Self::body(file).bind(client)
+    }
+
+    pub fn body(file: FileId) -> QueryBody<Self> {
+        QueryBody::new(Self { file })
     }
 }
 
@@ -1,6 +1,6 @@
 use crate::{
     proto::{self, Query::Query_oneof_query, QueryHeader::QueryHeader, ToProto},
-    query::{Query, QueryResponse, ToQueryProto},
+    query::{Query, QueryBody, QueryResponse, ToQueryProto},
     Client, FileId, FileInfo,
 };
 use failure::Error;
@@ -12,7 +12,11 @@ pub struct QueryFileGetInfo {
 
 impl QueryFileGetInfo {
     pub fn new(client: &Client, file: FileId) -> Query<Self> {
-        Query::new(client, Self { file })
+        Self::body(file).bind(client)
+    }
+
+    pub fn body(file: FileId) -> QueryBody<Self> {
+        QueryBody::new(Self { file })
     }
 }
 
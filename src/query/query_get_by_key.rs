@@ -2,7 +2,7 @@ use crate::{
     crypto::PublicKey,
     entity::try_into_entities,
     proto::{self, Query::Query_oneof_query, QueryHeader::QueryHeader, ToProto},
-    query::{Query, QueryResponse, ToQueryProto},
+    query::{Query, QueryBody, QueryResponse, ToQueryProto},
     Client, Entity,
 };
 use failure::Error;
@@ -13,7 +13,11 @@ pub struct QueryGetByKey {
 
 impl QueryGetByKey {
     pub fn new(client: &Client, key: PublicKey) -> Query<Self> {
-        Query::new(client, Self { key })
+        Self::body(key).bind(client)
+    }
+
+    pub fn body(key: PublicKey) -> QueryBody<Self> {
+        QueryBody::new(Self { key })
     }
 }
 
@@ -1,17 +1,27 @@
 use crate::{
     proto::{self, Query::Query_oneof_query, QueryHeader::QueryHeader, ToProto},
-    query::{Query, QueryResponse, ToQueryProto},
-    Client, TransactionId, TransactionReceipt,
+    query::{Query, QueryBody, QueryResponse, ToQueryProto},
+    Client, ErrorKind, Status, TransactionId, TransactionReceipt,
 };
+use chrono::{DateTime, Duration, Utc};
 use failure::Error;
 
+// Receipts are kept by the network for 3 minutes after consensus. Consensus must be reached
+// within the valid duration of the transaction (at most 2 minutes) from its valid start.
+const RECEIPT_RETENTION_SECS: i64 = 180;
+const MAX_VALID_DURATION_SECS: i64 = 120;
+
 pub struct QueryTransactionGetReceipt {
     transaction_id: TransactionId,
 }
 
 impl QueryTransactionGetReceipt {
     pub fn new(client: &Client, transaction_id: TransactionId) -> Query<Self> {
-        Query::new(client, Self { transaction_id })
+        Self::body(transaction_id).bind(client)
+    }
+
+    pub fn body(transaction_id: TransactionId) -> QueryBody<Self> {
+        QueryBody::new(Self { transaction_id })
     }
 }
 
@@ -35,4 +45,20 @@ impl ToQueryProto for QueryTransactionGetReceipt {
 
         Ok(Query_oneof_query::transactionGetReceipt(query))
     }
+
+    fn map_error(&self, error: Error, now: DateTime<Utc>) -> Error {
+        let not_found = match error.downcast_ref() {
+            Some(ErrorKind::PreCheck(Status::ReceiptNotFound)) => true,
+            _ => false,
+        };
+
+        let expires_at = self.transaction_id.transaction_valid_start
+            + Duration::seconds(MAX_VALID_DURATION_SECS + RECEIPT_RETENTION_SECS);
+
+        if not_found && now > expires_at {
+            ErrorKind::ReceiptExpired(self.transaction_id).into()
+        } else {
+            error
+        }
+    }
 }
@@ -1,18 +1,39 @@
 use crate::{
     proto::{self, Query::Query_oneof_query, QueryHeader::QueryHeader, ToProto},
-    query::{Query, QueryResponse, ToQueryProto},
-    Client, TransactionId, TransactionRecord
+    query::{Query, QueryBody, QueryResponse, ToQueryProto},
+    Client, TransactionId, TransactionRecord,
 };
 use failure::Error;
+use std::any::Any;
 use try_from::TryInto;
 
 pub struct QueryTransactionGetRecord {
     transaction: TransactionId,
+    force: bool,
 }
 
 impl QueryTransactionGetRecord {
     pub fn new(client: &Client, transaction: TransactionId) -> Query<Self> {
-        Query::new(client, Self { transaction })
+        Self::body(transaction).bind(client)
+    }
+
+    pub fn body(transaction: TransactionId) -> QueryBody<Self> {
+        QueryBody::new(Self {
+            transaction,
+            force: false,
+        })
+    }
+}
+
+impl Query<QueryTransactionGetRecord> {
+    /// Pay for the query even if the network has no receipt for the transaction yet.
+    ///
+    /// By default the (free) receipt is checked first, and the query fails with
+    /// `ErrorKind::RecordNotGenerated` while the transaction is unknown to the network or
+    /// has not reached consensus.
+    pub fn force(&mut self) -> &mut Self {
+        self.inner().force = true;
+        self
     }
 }
 
@@ -35,4 +56,16 @@ impl ToQueryProto for QueryTransactionGetRecord {
 
         Ok(Query_oneof_query::transactionGetRecord(query))
     }
+
+    fn receipt_required(&self) -> Option<TransactionId> {
+        if self.force {
+            None
+        } else {
+            Some(self.transaction)
+        }
+    }
+
+    fn as_any_mut(&mut self) -> Option<&mut dyn Any> {
+        Some(self)
+    }
 }
@@ -0,0 +1,54 @@
+use crate::{ErrorKind, Status};
+use std::{sync::Arc, time::Duration};
+
+/// Called before each retry with the attempt number, the status that caused the
+/// retry and the delay before the next attempt.
+pub(crate) type OnRetry = Arc<dyn Fn(usize, Status, Duration) + Send + Sync>;
+
+// Number of times a request is re-sent after a BUSY response before giving up
+pub(crate) const MAX_ATTEMPTS: usize = 5;
+
+#[inline]
+pub(crate) fn backoff(attempt: usize) -> Duration {
+    Duration::from_secs((attempt * 2) as u64)
+}
+
+// Number of times a request is re-sent on a new connection after its connection broke
+pub(crate) const MAX_RECONNECTS: usize = 3;
+
+// Does the error mean the connection to the node is gone, rather than the request failing?
+pub(crate) fn is_connection_error(error: &grpc::Error) -> bool {
+    match error {
+        grpc::Error::Io(_) | grpc::Error::Http(_) => true,
+        _ => false,
+    }
+}
+
+// gRPC status for a method the node does not implement; the request was never handled
+const GRPC_UNIMPLEMENTED: i32 = 12;
+
+// Did the request definitely not reach the node?
+pub(crate) fn is_not_sent(error: &grpc::Error) -> bool {
+    match error {
+        grpc::Error::GrpcMessage(message) => message.grpc_status == GRPC_UNIMPLEMENTED,
+
+        // the connection was refused, so nothing was written to it
+        grpc::Error::Io(error) => error.kind() == std::io::ErrorKind::ConnectionRefused,
+
+        _ => false,
+    }
+}
+
+/// Describe a transport error, with whether the request definitely did not reach the node.
+pub(crate) fn transport_error(error: grpc::Error) -> ErrorKind {
+    let code = match &error {
+        grpc::Error::GrpcMessage(message) => Some(message.grpc_status),
+        _ => None,
+    };
+
+    ErrorKind::TransportError {
+        code,
+        message: error.to_string(),
+        not_sent: is_not_sent(&error),
+    }
+}
@@ -0,0 +1,79 @@
+use crate::{
+    proto::{Query::Query_oneof_query, TransactionBody::TransactionBody_oneof_data},
+    AccountId,
+};
+use std::collections::HashMap;
+
+/// The gRPC services of a node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Service {
+    /// Accounts, transfers, claims, and receipts and records of transactions.
+    Crypto,
+    File,
+    Contract,
+    Freeze,
+}
+
+impl Service {
+    fn of_query(query: &Query_oneof_query) -> Self {
+        use self::Query_oneof_query::*;
+
+        match query {
+            fileGetContents(_) | fileGetInfo(_) => Service::File,
+
+            getBySolidityID(_)
+            | contractCallLocal(_)
+            | contractGetInfo(_)
+            | contractGetBytecode(_)
+            | ContractGetRecords(_) => Service::Contract,
+
+            _ => Service::Crypto,
+        }
+    }
+
+    fn of_transaction(data: &TransactionBody_oneof_data) -> Self {
+        use self::TransactionBody_oneof_data::*;
+
+        match data {
+            fileCreate(_) | fileAppend(_) | fileUpdate(_) | fileDelete(_) => Service::File,
+
+            contractCall(_)
+            | contractCreateInstance(_)
+            | contractUpdateInstance(_)
+            | contractDeleteInstance(_) => Service::Contract,
+
+            systemDelete(body) if body.has_contractID() => Service::Contract,
+            systemUndelete(body) if body.has_contractID() => Service::Contract,
+            systemDelete(_) | systemUndelete(_) => Service::File,
+
+            freeze(_) => Service::Freeze,
+
+            _ => Service::Crypto,
+        }
+    }
+}
+
+/// The nodes that requests are sent to when none is set on the request itself,
+/// overriding the default node of the client.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Routes {
+    pub(crate) queries: Option<AccountId>,
+    pub(crate) transactions: Option<AccountId>,
+
+    // used for both queries and transactions of the service; takes precedence over the above
+    pub(crate) services: HashMap<Service, AccountId>,
+}
+
+impl Routes {
+    pub(crate) fn query_node(&self, query: &Query_oneof_query) -> Option<AccountId> {
+        let service = Service::of_query(query);
+
+        self.services.get(&service).copied().or(self.queries)
+    }
+
+    pub(crate) fn transaction_node(&self, data: &TransactionBody_oneof_data) -> Option<AccountId> {
+        let service = Service::of_transaction(data);
+
+        self.services.get(&service).copied().or(self.transactions)
+    }
+}
@@ -0,0 +1,120 @@
+use crate::{proto, AccountId, ContractId, ErrorKind, FileId};
+use failure::Error;
+
+use crate::proto::TransactionBody::TransactionBody_oneof_data::*;
+
+// (shard, realm, the ID as text) of an entity a transaction refers to
+type Reference = (i64, i64, String);
+
+fn account(id: &proto::BasicTypes::AccountID) -> Reference {
+    let id = AccountId::from(id.clone());
+    (id.shard, id.realm, id.to_string())
+}
+
+fn file(id: &proto::BasicTypes::FileID) -> Reference {
+    let id = FileId::from(id.clone());
+    (id.shard, id.realm, id.to_string())
+}
+
+fn contract(id: &proto::BasicTypes::ContractID) -> Reference {
+    let id = ContractId::from(id.clone());
+    (id.shard, id.realm, id.to_string())
+}
+
+// Every account, file and contract that a transaction refers to, including its payer and node
+fn references(body: &proto::TransactionBody::TransactionBody) -> Vec<Reference> {
+    let mut references = vec![
+        account(body.get_transactionID().get_accountID()),
+        account(body.get_nodeAccountID()),
+    ];
+
+    match &body.data {
+        Some(cryptoTransfer(data)) => references.extend(
+            data.get_transfers()
+                .get_accountAmounts()
+                .iter()
+                .map(|amount| account(amount.get_accountID())),
+        ),
+
+        Some(cryptoCreateAccount(data)) if data.has_proxyAccountID() => {
+            references.push(account(data.get_proxyAccountID()));
+        }
+
+        Some(cryptoUpdateAccount(data)) => {
+            references.push(account(data.get_accountIDToUpdate()));
+
+            if data.has_proxyAccountID() {
+                references.push(account(data.get_proxyAccountID()));
+            }
+        }
+
+        Some(cryptoDelete(data)) => {
+            references.push(account(data.get_deleteAccountID()));
+
+            if data.has_transferAccountID() {
+                references.push(account(data.get_transferAccountID()));
+            }
+        }
+
+        Some(cryptoAddClaim(data)) => references.push(account(data.get_claim().get_accountID())),
+        Some(cryptoDeleteClaim(data)) => references.push(account(data.get_accountIDToDeleteFrom())),
+
+        Some(contractCall(data)) => references.push(contract(data.get_contractID())),
+
+        Some(contractCreateInstance(data)) => {
+            references.push(file(data.get_fileID()));
+
+            if data.has_proxyAccountID() {
+                references.push(account(data.get_proxyAccountID()));
+            }
+        }
+
+        Some(contractUpdateInstance(data)) => {
+            references.push(contract(data.get_contractID()));
+
+            if data.has_fileID() {
+                references.push(file(data.get_fileID()));
+            }
+
+            if data.has_proxyAccountID() {
+                references.push(account(data.get_proxyAccountID()));
+            }
+        }
+
+        Some(contractDeleteInstance(data)) => {
+            references.push(contract(data.get_contractID()));
+
+            if data.has_transferAccountID() {
+                references.push(account(data.get_transferAccountID()));
+            }
+        }
+
+        Some(fileAppend(data)) => references.push(file(data.get_fileID())),
+        Some(fileUpdate(data)) => references.push(file(data.get_fileID())),
+        Some(fileDelete(data)) => references.push(file(data.get_fileID())),
+
+        Some(systemDelete(data)) if data.has_fileID() => references.push(file(data.get_fileID())),
+        Some(systemDelete(data)) => references.push(contract(data.get_contractID())),
+        Some(systemUndelete(data)) if data.has_fileID() => references.push(file(data.get_fileID())),
+        Some(systemUndelete(data)) => references.push(contract(data.get_contractID())),
+
+        _ => {}
+    }
+
+    references
+}
+
+/// Check that every entity a transaction refers to is in the shard and realm of the network.
+pub(crate) fn check(
+    body: &proto::TransactionBody::TransactionBody,
+    shard: i64,
+    realm: i64,
+) -> Result<(), Error> {
+    for (id_shard, id_realm, id) in references(body) {
+        if (id_shard, id_realm) != (shard, realm) {
+            Err(ErrorKind::WrongShardRealm { id, shard, realm })?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,173 @@
+use crate::{
+    crypto::{PublicKey, Signature},
+    proto::{self, ToProto},
+    transaction::{Transaction, TransactionRaw},
+    ErrorKind,
+};
+use failure::Error;
+use protobuf::RepeatedField;
+
+/// The structure of keys that must sign a transaction.
+#[derive(Debug, Clone)]
+pub enum RequiredKey {
+    /// A single key that must sign.
+    Key(PublicKey),
+
+    /// Every key in the list must be satisfied.
+    List(Vec<RequiredKey>),
+
+    /// At least `threshold` of the keys must be satisfied.
+    Threshold {
+        threshold: usize,
+        keys: Vec<RequiredKey>,
+    },
+}
+
+impl RequiredKey {
+    fn contains(&self, public: &PublicKey) -> bool {
+        match self {
+            RequiredKey::Key(key) => key == public,
+            RequiredKey::List(keys) | RequiredKey::Threshold { keys, .. } => {
+                keys.iter().any(|key| key.contains(public))
+            }
+        }
+    }
+
+    fn is_satisfied(&self, signatures: &[(PublicKey, Signature)]) -> bool {
+        match self {
+            RequiredKey::Key(key) => signatures.iter().any(|(public, _)| public == key),
+            RequiredKey::List(keys) => keys.iter().all(|key| key.is_satisfied(signatures)),
+            RequiredKey::Threshold { threshold, keys } => {
+                keys.iter()
+                    .filter(|key| key.is_satisfied(signatures))
+                    .count()
+                    >= *threshold
+            }
+        }
+    }
+
+    // Arrange the collected signatures to mirror the key structure; keys that have not
+    // signed get a null signature
+    fn to_signature(
+        &self,
+        signatures: &[(PublicKey, Signature)],
+    ) -> Result<proto::BasicTypes::Signature, Error> {
+        let list = |keys: &[RequiredKey]| -> Result<_, Error> {
+            let mut list = proto::BasicTypes::SignatureList::new();
+            list.set_sigs(RepeatedField::from_vec(
+                keys.iter()
+                    .map(|key| key.to_signature(signatures))
+                    .collect::<Result<Vec<_>, _>>()?,
+            ));
+
+            Ok(list)
+        };
+
+        let mut signature = proto::BasicTypes::Signature::new();
+
+        match self {
+            RequiredKey::Key(key) => {
+                if let Some((_, sig)) = signatures.iter().find(|(public, _)| public == key) {
+                    signature = sig.to_proto()?;
+                }
+            }
+
+            RequiredKey::List(keys) => signature.set_signatureList(list(keys)?),
+
+            RequiredKey::Threshold { keys, .. } => {
+                let mut threshold = proto::BasicTypes::ThresholdSignature::new();
+                threshold.set_sigs(list(keys)?);
+                signature.set_thresholdSignature(threshold);
+            }
+        }
+
+        Ok(signature)
+    }
+}
+
+impl From<PublicKey> for RequiredKey {
+    fn from(key: PublicKey) -> Self {
+        RequiredKey::Key(key)
+    }
+}
+
+/// Collects the signatures of several parties for a frozen transaction.
+///
+/// Each party signs `body_bytes()` on their own and hands back the signature; once
+/// `is_complete()` returns true, `finish()` produces the transaction ready to execute.
+pub struct SignatureCollector<T> {
+    transaction: Transaction<T, TransactionRaw>,
+    required: RequiredKey,
+    signatures: Vec<(PublicKey, Signature)>,
+}
+
+impl<T: 'static> SignatureCollector<T> {
+    pub fn new(
+        mut transaction: Transaction<T, TransactionRaw>,
+        required: impl Into<RequiredKey>,
+    ) -> Result<Self, Error> {
+        if transaction.body_bytes().is_none() {
+            // the transaction failed to build; surface the reason
+            transaction.take_raw()?;
+        }
+
+        Ok(Self {
+            transaction,
+            required: required.into(),
+            signatures: Vec::new(),
+        })
+    }
+
+    /// The bytes each party must sign.
+    pub fn body_bytes(&self) -> &[u8] {
+        // note: checked on construction
+        self.transaction.body_bytes().unwrap()
+    }
+
+    /// Add the signature of `public` over `body_bytes()`.
+    ///
+    /// Signatures from keys that are not required, or that do not verify, are rejected.
+    /// Adding a second signature from the same key replaces the first.
+    pub fn add_signature(
+        &mut self,
+        public: PublicKey,
+        signature: impl AsRef<[u8]>,
+    ) -> Result<&mut Self, Error> {
+        if !self.required.contains(&public) {
+            Err(ErrorKind::UnexpectedSigner(public))?;
+        }
+
+        let signature = Signature::from_bytes(signature)?;
+
+        if !public.verify(self.body_bytes(), &signature)? {
+            Err(ErrorKind::InvalidSignature(public))?;
+        }
+
+        self.signatures.retain(|(key, _)| key != &public);
+        self.signatures.push((public, signature));
+
+        Ok(self)
+    }
+
+    /// The keys that have signed so far.
+    pub fn signed(&self) -> impl Iterator<Item = &PublicKey> {
+        self.signatures.iter().map(|(public, _)| public)
+    }
+
+    /// Have enough keys signed to satisfy the required key structure?
+    pub fn is_complete(&self) -> bool {
+        self.required.is_satisfied(&self.signatures)
+    }
+
+    /// Attach the collected signatures to the transaction.
+    pub fn finish(mut self) -> Result<Transaction<T, TransactionRaw>, Error> {
+        if !self.is_complete() {
+            Err(ErrorKind::MissingField("signatures"))?;
+        }
+
+        let signature = self.required.to_signature(&self.signatures)?;
+        self.transaction.push_signature_proto(signature);
+
+        Ok(self.transaction)
+    }
+}
@@ -0,0 +1,73 @@
+use crate::proto;
+
+use crate::proto::TransactionBody::TransactionBody_oneof_data::*;
+
+/// How a signature must be nested to satisfy the key it is for.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub(crate) enum SignatureShape {
+    /// A bare signature, for a single key.
+    Key,
+
+    /// A signature wrapped in a `SignatureList`, for a `KeyList` of one key.
+    KeyList,
+}
+
+// The shape of the signature at `index` in the signature map of a transaction with `body`.
+//
+// The first signature is always from the payer. Any signatures after it are for the
+// keys of the entity the transaction acts on, e.g. the keys of the file being appended
+// to or the admin key of the contract being updated.
+pub(crate) fn signature_shape(
+    body: &proto::TransactionBody::TransactionBody,
+    index: usize,
+) -> SignatureShape {
+    if index == 0 {
+        return payer_shape(body);
+    }
+
+    match body.data {
+        // the keys of a file are always a `KeyList`
+        Some(fileCreate(_)) | Some(fileAppend(_)) | Some(fileUpdate(_)) | Some(fileDelete(_)) => {
+            SignatureShape::KeyList
+        }
+
+        // contracts have a single admin key; accounts a single key, and an account update
+        // that replaces the key is signed by both the old (#1) and the new key (#2)
+        _ => SignatureShape::Key,
+    }
+}
+
+fn payer_shape(body: &proto::TransactionBody::TransactionBody) -> SignatureShape {
+    let payer = body.get_transactionID().get_accountID();
+
+    // the system accounts (num < 1000) are created at genesis with a `KeyList` of one key
+    if payer.get_accountNum() < 1000 {
+        SignatureShape::KeyList
+    } else {
+        SignatureShape::Key
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{signature_shape, SignatureShape};
+    use crate::proto;
+
+    #[test]
+    fn test_signature_shape() {
+        let mut body = proto::TransactionBody::TransactionBody::new();
+        body.mut_transactionID().mut_accountID().set_accountNum(1001);
+        body.set_fileAppend(proto::FileAppend::FileAppendTransactionBody::new());
+
+        assert_eq!(signature_shape(&body, 0), SignatureShape::Key);
+        assert_eq!(signature_shape(&body, 1), SignatureShape::KeyList);
+
+        body.mut_transactionID().mut_accountID().set_accountNum(2);
+        body.set_contractUpdateInstance(
+            proto::ContractUpdate::ContractUpdateTransactionBody::new(),
+        );
+
+        assert_eq!(signature_shape(&body, 0), SignatureShape::KeyList);
+        assert_eq!(signature_shape(&body, 1), SignatureShape::Key);
+    }
+}
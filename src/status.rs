@@ -2,7 +2,7 @@ use crate::proto;
 //use crate::status::Status::EmptyClaimHash;
 //use test::TestFn::{StaticBenchFn, StaticTestFn};
 
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 #[repr(u8)]
 pub enum Status {
     // the transaction passed the precheck
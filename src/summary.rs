@@ -0,0 +1,277 @@
+use crate::{proto, AccountId, ContractId, FileId, PublicKey, TransactionId};
+use chrono::{DateTime, Utc};
+use itertools::Itertools;
+use std::fmt::Write;
+use try_from::TryFrom;
+
+use crate::proto::TransactionBody::TransactionBody_oneof_data::*;
+
+// The name of the kind of transaction, as it is called in the HAPI protobufs
+pub(crate) fn kind_name(body: &proto::TransactionBody::TransactionBody) -> &'static str {
+    match body.data {
+        Some(contractCall(_)) => "ContractCall",
+        Some(contractCreateInstance(_)) => "ContractCreate",
+        Some(contractUpdateInstance(_)) => "ContractUpdate",
+        Some(contractDeleteInstance(_)) => "ContractDelete",
+        Some(cryptoAddClaim(_)) => "CryptoAddClaim",
+        Some(cryptoCreateAccount(_)) => "CryptoCreate",
+        Some(cryptoDelete(_)) => "CryptoDelete",
+        Some(cryptoDeleteClaim(_)) => "CryptoDeleteClaim",
+        Some(cryptoTransfer(_)) => "CryptoTransfer",
+        Some(cryptoUpdateAccount(_)) => "CryptoUpdate",
+        Some(fileAppend(_)) => "FileAppend",
+        Some(fileCreate(_)) => "FileCreate",
+        Some(fileDelete(_)) => "FileDelete",
+        Some(fileUpdate(_)) => "FileUpdate",
+        Some(systemDelete(_)) => "SystemDelete",
+        Some(systemUndelete(_)) => "SystemUndelete",
+        Some(freeze(_)) => "Freeze",
+        None => "Empty",
+    }
+}
+
+// Format an amount of tinybar as hbar, dropping trailing zeros of the fraction
+//
+// Amounts may come from transactions that are not trusted yet, so any `i64` or `u64` is
+// formatted as it is, without overflowing
+pub(crate) fn hbar(tinybar: impl Into<i128>) -> String {
+    let tinybar = tinybar.into();
+    let sign = if tinybar < 0 { "-" } else { "" };
+    let tinybar = tinybar.abs();
+    let (whole, fraction) = (tinybar / 100_000_000, tinybar % 100_000_000);
+
+    if fraction == 0 {
+        format!("{}{} ℏ", sign, whole)
+    } else {
+        let fraction = format!("{:08}", fraction);
+        format!("{}{}.{} ℏ", sign, whole, fraction.trim_end_matches('0'))
+    }
+}
+
+// Format a key as its public key, if it is (or wraps) a single ed25519 key
+fn key(key: &proto::BasicTypes::Key) -> String {
+    PublicKey::try_from(key.clone())
+        .map(|key| key.to_string())
+        .unwrap_or_else(|_| "<complex key>".to_owned())
+}
+
+// The details specific to each kind of transaction, if there are any worth showing
+fn details(body: &proto::TransactionBody::TransactionBody) -> Option<String> {
+    match &body.data {
+        Some(cryptoTransfer(data)) => {
+            let transfers: Vec<(AccountId, i64)> = data.get_transfers().clone().into();
+
+            // every account with what it gets (or gives), so that it is clear who is paid what
+            Some(
+                transfers
+                    .iter()
+                    .map(|(id, amount)| {
+                        let sign = if *amount > 0 { "+" } else { "" };
+                        format!("{} {}{}", id, sign, hbar(*amount))
+                    })
+                    .join(", "),
+            )
+        }
+
+        Some(cryptoCreateAccount(data)) => {
+            Some(format!("initial balance {}", hbar(data.get_initialBalance())))
+        }
+
+        Some(cryptoDelete(data)) => Some(format!(
+            "delete {}, transfer to {}",
+            AccountId::from(data.get_deleteAccountID().clone()),
+            AccountId::from(data.get_transferAccountID().clone())
+        )),
+
+        Some(cryptoUpdateAccount(data)) => {
+            let mut details = vec![format!(
+                "update {}",
+                AccountId::from(data.get_accountIDToUpdate().clone())
+            )];
+
+            if data.has_key() {
+                details.push(format!("key {}", key(data.get_key())));
+            }
+
+            if data.has_expirationTime() {
+                let expiry: DateTime<Utc> = data.get_expirationTime().clone().into();
+                details.push(format!("expires {}", expiry));
+            }
+
+            Some(details.join(", "))
+        }
+
+        Some(contractCall(data)) => Some(format!(
+            "call {}, gas {}, amount {}",
+            ContractId::from(data.get_contractID().clone()),
+            data.get_gas(),
+            hbar(data.get_amount())
+        )),
+
+        Some(contractCreateInstance(data)) => Some(format!(
+            "bytecode {}, gas {}, initial balance {}",
+            FileId::from(data.get_fileID().clone()),
+            data.get_gas(),
+            hbar(data.get_initialBalance())
+        )),
+
+        Some(fileAppend(data)) => Some(format!(
+            "append {} bytes to {}",
+            data.get_contents().len(),
+            FileId::from(data.get_fileID().clone())
+        )),
+
+        Some(fileCreate(data)) => Some(format!("{} bytes", data.get_contents().len())),
+
+        Some(fileDelete(data)) => Some(format!(
+            "delete {}",
+            FileId::from(data.get_fileID().clone())
+        )),
+
+        Some(fileUpdate(data)) => {
+            let mut details = vec![format!(
+                "update {}",
+                FileId::from(data.get_fileID().clone())
+            )];
+
+            if data.has_keys() {
+                let keys = data.get_keys().get_keys().iter().map(key).join(" ");
+                details.push(format!("keys [{}]", keys));
+            }
+
+            if data.has_expirationTime() {
+                let expiry: DateTime<Utc> = data.get_expirationTime().clone().into();
+                details.push(format!("expires {}", expiry));
+            }
+
+            if !data.get_contents().is_empty() {
+                details.push(format!("{} bytes", data.get_contents().len()));
+            }
+
+            Some(details.join(", "))
+        }
+
+        Some(systemDelete(data)) => {
+            let expiry: DateTime<Utc> = data.get_expirationTime().clone().into();
+
+            let entity = if data.has_contractID() {
+                ContractId::from(data.get_contractID().clone()).to_string()
+            } else {
+                FileId::from(data.get_fileID().clone()).to_string()
+            };
+
+            Some(format!("delete {}, expires {}", entity, expiry))
+        }
+
+        Some(freeze(data)) => Some(format!(
+            "freeze from {:02}:{:02} to {:02}:{:02} UTC",
+            data.get_startHour(),
+            data.get_startMin(),
+            data.get_endHour(),
+            data.get_endMin()
+        )),
+
+        _ => None,
+    }
+}
+
+// A one-line human-readable description of a transaction body
+pub(crate) fn summarize(body: &proto::TransactionBody::TransactionBody) -> String {
+    let id: TransactionId = body.get_transactionID().clone().into();
+    let node: AccountId = body.get_nodeAccountID().clone().into();
+
+    let mut summary = kind_name(body).to_owned();
+
+    if let Some(details) = details(body) {
+        // note: writing to a string cannot fail
+        write!(summary, ": {},", details).unwrap();
+    } else {
+        summary.push(':');
+    }
+
+    write!(
+        summary,
+        " fee ≤ {}, node {}, id {}",
+        hbar(body.get_transactionFee()),
+        node,
+        id
+    )
+    .unwrap();
+
+    if !body.get_memo().is_empty() {
+        write!(summary, ", memo {:?}", body.get_memo()).unwrap();
+    }
+
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{details, hbar};
+    use crate::{proto, proto::ToProto, AccountId, FileId, SecretKey};
+    use chrono::{TimeZone, Utc};
+    use failure::Error;
+
+    #[test]
+    fn test_hbar() {
+        assert_eq!(hbar(100_000_000), "1 ℏ");
+        assert_eq!(hbar(150_000_000), "1.5 ℏ");
+        assert_eq!(hbar(1), "0.00000001 ℏ");
+        assert_eq!(hbar(-250_000_000), "-2.5 ℏ");
+        assert_eq!(hbar(i64::min_value()), "-92233720368.54775808 ℏ");
+        assert_eq!(hbar(u64::max_value()), "184467440737.09551615 ℏ");
+    }
+
+    #[test]
+    fn test_transfer_lists_each_account() -> Result<(), Error> {
+        let mut transfers = proto::CryptoTransfer::TransferList::new();
+
+        for (id, amount) in &[(1001, -300_000_000), (1002, 100_000_000), (1003, i64::min_value())] {
+            let mut pb = proto::CryptoTransfer::AccountAmount::new();
+            pb.set_accountID(AccountId::new(0, 0, *id).to_proto()?);
+            pb.set_amount(*amount);
+            transfers.mut_accountAmounts().push(pb);
+        }
+
+        let mut data = proto::CryptoTransfer::CryptoTransferTransactionBody::new();
+        data.set_transfers(transfers);
+
+        let mut body = proto::TransactionBody::TransactionBody::new();
+        body.set_cryptoTransfer(data);
+
+        assert_eq!(
+            details(&body).unwrap(),
+            "0:0:1001 -3 ℏ, 0:0:1002 +1 ℏ, 0:0:1003 -92233720368.54775808 ℏ"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_file_update_lists_changes() -> Result<(), Error> {
+        let public = SecretKey::generate("").0.public();
+        let expiry = Utc.ymd(2019, 10, 1).and_hms(12, 0, 0);
+
+        let mut keys = proto::BasicTypes::KeyList::new();
+        keys.mut_keys().push(public.to_proto()?);
+
+        let mut data = proto::FileUpdate::FileUpdateTransactionBody::new();
+        data.set_fileID(FileId::new(0, 0, 1001).to_proto()?);
+        data.set_keys(keys);
+        data.set_expirationTime(expiry.to_proto()?);
+        data.set_contents(b"hello".to_vec());
+
+        let mut body = proto::TransactionBody::TransactionBody::new();
+        body.set_fileUpdate(data);
+
+        assert_eq!(
+            details(&body).unwrap(),
+            format!(
+                "update 0:0:1001, keys [{}], expires 2019-10-01 12:00:00 UTC, 5 bytes",
+                public
+            )
+        );
+
+        Ok(())
+    }
+}
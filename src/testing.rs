@@ -0,0 +1,38 @@
+//! Helpers for tests and examples that run against a test network.
+
+use crate::{AccountId, Client, ErrorKind, SecretKey, Status};
+use failure::Error;
+use futures::Future;
+
+/// Generate a key and create an account for it holding `initial_balance` tinybar, paid for
+/// by the operator of `client`, once the receipt shows it was created.
+pub fn bootstrap_account(
+    client: &Client,
+    initial_balance: u64,
+) -> Result<(AccountId, SecretKey), Error> {
+    crate::RUNTIME
+        .lock()
+        .block_on(bootstrap_account_async(client, initial_balance))
+}
+
+pub fn bootstrap_account_async(
+    client: &Client,
+    initial_balance: u64,
+) -> impl Future<Output = Result<(AccountId, SecretKey), Error>> {
+    let client = client.clone();
+    let (secret, _) = SecretKey::generate("");
+    let mut create = client.create_account();
+
+    create.key(secret.public()).initial_balance(initial_balance);
+
+    async move {
+        let id = create.execute_async().await?;
+        let receipt = client.transaction(id).wait_for_receipt_async().await?;
+
+        if receipt.status != Status::Success {
+            Err(ErrorKind::ReceiptStatus(receipt.status))?;
+        }
+
+        Ok((receipt.account_id()?, secret))
+    }
+}
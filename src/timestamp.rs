@@ -2,22 +2,35 @@ use crate::{
     error::ErrorKind,
     proto::{self, ToProto},
 };
-use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+use chrono::{
+    naive::{MAX_DATE, MIN_DATE},
+    DateTime, NaiveDateTime, TimeZone, Utc,
+};
 use failure::Error;
 use itertools::Itertools;
 use std::str::FromStr;
 use try_from::TryInto;
 
 #[repr(C)]
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub(crate) struct Timestamp(pub(crate) i64, pub(crate) i32);
 
+/// Timestamps outside of what `DateTime` can represent (or with nanos outside of
+/// `0..1_000_000_000`) saturate rather than panic, as they may come from the network.
 impl From<Timestamp> for DateTime<Utc> {
+    #[allow(clippy::cast_sign_loss)]
     fn from(Timestamp(seconds, nanos): Timestamp) -> Self {
-        Utc.from_utc_datetime(&NaiveDateTime::from_timestamp(
-            seconds,
-            nanos.try_into().unwrap(),
-        ))
+        let nanos = nanos.max(0).min(999_999_999) as u32;
+
+        let time = NaiveDateTime::from_timestamp_opt(seconds, nanos).unwrap_or_else(|| {
+            if seconds < 0 {
+                MIN_DATE.and_hms(0, 0, 0)
+            } else {
+                MAX_DATE.and_hms_nano(23, 59, 59, 999_999_999)
+            }
+        });
+
+        Utc.from_utc_datetime(&time)
     }
 }
 
@@ -36,6 +49,12 @@ impl From<proto::Timestamp::Timestamp> for DateTime<Utc> {
     }
 }
 
+impl From<proto::Timestamp::TimestampSeconds> for DateTime<Utc> {
+    fn from(dt: proto::Timestamp::TimestampSeconds) -> Self {
+        Timestamp(dt.get_seconds(), 0).into()
+    }
+}
+
 impl ToProto<proto::Timestamp::Timestamp> for DateTime<Utc> {
     fn to_proto(&self) -> Result<proto::Timestamp::Timestamp, Error> {
         let mut timestamp = proto::Timestamp::Timestamp::new();
@@ -66,3 +85,27 @@ impl FromStr for Timestamp {
         Ok(Timestamp(seconds.parse()?, nanos.parse()?))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Timestamp;
+    use chrono::{DateTime, Utc};
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn test_any_timestamp_converts(seconds in any::<i64>(), nanos in any::<i32>()) {
+            let _: DateTime<Utc> = Timestamp(seconds, nanos).into();
+        }
+
+        #[test]
+        fn test_round_trip(
+            seconds in -10_000_000_000i64..10_000_000_000,
+            nanos in 0..1_000_000_000
+        ) {
+            let time: DateTime<Utc> = Timestamp(seconds, nanos).into();
+
+            prop_assert_eq!(Timestamp::from(time), Timestamp(seconds, nanos));
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use crate::{AccountId, Status};
+use parking_lot::Mutex;
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// A step in executing a transaction or query.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TraceStep {
+    /// The transaction body (or the payment of a query) was built. A transaction may have
+    /// been built before the trace started, e.g. to be signed offline.
+    Build,
+
+    /// The operator signature was added.
+    Sign,
+
+    /// The request was sent to `node`; `attempt` is 0 for the first send.
+    Submit { attempt: usize, node: AccountId },
+
+    /// The node was busy; the request is sent again after `delay`.
+    Retry {
+        attempt: usize,
+        status: Status,
+        delay: Duration,
+    },
+
+    /// The connection to the node broke and was re-established.
+    Reconnect,
+
+    /// The node answered with the pre-check `status`.
+    Response { status: Status },
+}
+
+#[derive(Debug, Clone)]
+pub struct TraceEvent {
+    pub at: Instant,
+    pub step: TraceStep,
+}
+
+/// When each step of executing a transaction or query happened, from `execute_traced`
+/// or `get_traced`.
+#[derive(Debug, Clone)]
+pub struct ExecutionTrace {
+    pub started: Instant,
+    pub events: Vec<TraceEvent>,
+
+    /// The tags of the transaction, from `Transaction::tag`; queries have none.
+    pub tags: Vec<(String, String)>,
+}
+
+impl ExecutionTrace {
+    pub(crate) fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            events: Vec::new(),
+            tags: Vec::new(),
+        }
+    }
+
+    /// The time from the start of execution to the last step.
+    pub fn elapsed(&self) -> Duration {
+        self.events
+            .last()
+            .map_or(Duration::from_secs(0), |event| event.at - self.started)
+    }
+}
+
+// A trace being recorded, shared with the future executing the request
+pub(crate) type Tracer = Arc<Mutex<ExecutionTrace>>;
+
+#[inline]
+pub(crate) fn record(tracer: &Option<Tracer>, step: TraceStep) {
+    if let Some(tracer) = tracer {
+        tracer.lock().events.push(TraceEvent {
+            at: Instant::now(),
+            step,
+        });
+    }
+}
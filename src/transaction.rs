@@ -1,5 +1,10 @@
-//mod transaction_admin_delete;
-//mod transaction_admin_recover;
+//! Every transaction builder, and [`Transaction`] that signs and executes them.
+//!
+//! Transactions are usually started from a method on [`Client`](crate::Client).
+//! Builder types are kept at this path when their files move.
+
+mod transaction_admin_delete;
+mod transaction_admin_recover;
 mod transaction_contract_call;
 mod transaction_contract_create;
 mod transaction_contract_delete;
@@ -10,46 +15,79 @@ mod transaction_crypto_delete;
 mod transaction_crypto_delete_claim;
 mod transaction_crypto_transfer;
 mod transaction_crypto_update;
+#[cfg(feature = "proto")]
+mod transaction_custom;
 mod transaction_file_append;
 mod transaction_file_create;
 mod transaction_file_delete;
 mod transaction_file_update;
+mod transaction_freeze;
 
 pub use self::{
-    transaction_contract_call::*, transaction_contract_create::*, transaction_contract_update::*,
-    transaction_contract_delete::*, transaction_crypto_add_claim::*, transaction_crypto_create::*,
-    transaction_crypto_delete::*, transaction_crypto_delete_claim::*, transaction_crypto_transfer::*,
+    transaction_admin_delete::*, transaction_admin_recover::*, transaction_contract_call::*,
+    transaction_contract_create::*, transaction_contract_update::*, transaction_contract_delete::*,
+    transaction_crypto_add_claim::*, transaction_crypto_create::*, transaction_crypto_delete::*,
+    transaction_crypto_delete_claim::*, transaction_crypto_transfer::*,
     transaction_crypto_update::*, transaction_file_append::*, transaction_file_create::*,
-    transaction_file_delete::*, transaction_file_update::*,
+    transaction_file_delete::*, transaction_file_update::*, transaction_freeze::*,
 };
 
+#[cfg(feature = "proto")]
+pub use self::transaction_custom::TransactionCustom;
+
 use crate::{
-    crypto::SecretKey,
+    client::OperatorSecret,
+    clock::SharedClock,
+    crypto::{PublicKey, SecretKey, Signature},
     error::ErrorKind,
+    fee_policy::{self, FeePolicy},
+    health::Outcome,
+    network::Network,
+    payload_log,
     proto::{
-        self,
-        CryptoService_grpc::{CryptoService, CryptoServiceClient},
-        FileService_grpc::{FileService, FileServiceClient},
-        SmartContractService_grpc::{SmartContractService, SmartContractServiceClient},
+        self, CryptoService_grpc::CryptoService, FileService_grpc::FileService,
+        FreezeService_grpc::FreezeService, SmartContractService_grpc::SmartContractService,
         ToProto,
     },
-    AccountId, Client, TransactionId,
+    retry::{self, OnRetry},
+    routing::Routes,
+    shard_realm,
+    signing::{signature_shape, SignatureShape},
+    summary,
+    trace::{self, ExecutionTrace, TraceEvent, TraceStep, Tracer},
+    AccountId, Budget, Client, Memo, Status, TransactionId,
 };
 use futures::compat::Compat01As03;
+use chrono::{DateTime, Utc};
 use failure::Error;
 use futures::{Future,};
-use protobuf::Message;
+use parking_lot::Mutex;
+use protobuf::{Message, RepeatedField};
 use query_interface::Object;
-use std::{any::Any, marker::PhantomData, mem::swap, sync::Arc, time::Duration};
+use std::{
+    any::Any,
+    marker::PhantomData,
+    mem::swap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use crate::proto::TransactionBody::TransactionBody_oneof_data::*;
 
+// How long after its valid start the network accepts a transaction built by this crate
+pub(crate) const VALID_DURATION: Duration = Duration::from_secs(120);
+
 pub struct TransactionBuilder<T> {
     id: Option<TransactionId>,
     node: Option<AccountId>,
-    memo: Option<String>,
+    node_candidates: Vec<AccountId>,
+    default_node: Option<AccountId>,
+    routes: Arc<Routes>,
+    memo: Option<Memo>,
     generate_record: bool,
     fee: u64,
+    fee_policy: Option<FeePolicy>,
+    shard_realm: (i64, i64),
     pub(crate) inner: Box<dyn Object>,
     phantom: PhantomData<T>,
 }
@@ -59,6 +97,114 @@ pub struct TransactionRaw {
     pub(crate) tx: proto::Transaction::Transaction,
 }
 
+impl TransactionRaw {
+    fn signatures(&mut self) -> &mut RepeatedField<proto::BasicTypes::Signature> {
+        if !self.tx.has_sigs() {
+            self.tx.set_sigs(proto::BasicTypes::SignatureList::new());
+        }
+
+        // note: this cannot fail
+        &mut self.tx.sigs.as_mut().unwrap().sigs
+    }
+
+    // Has `signature` been added already, on its own or nested in a list?
+    fn has_signature(&mut self, signature: &Signature) -> bool {
+        fn contains(existing: &proto::BasicTypes::Signature, bytes: &[u8]) -> bool {
+            existing.get_ed25519() == bytes
+                || existing
+                    .get_signatureList()
+                    .get_sigs()
+                    .iter()
+                    .any(|nested| contains(nested, bytes))
+        }
+
+        let bytes = signature.to_bytes();
+        self.signatures().iter().any(|existing| contains(existing, &bytes))
+    }
+
+    // Signing twice with the same key has no effect. `operator_signs` is whether the
+    // operator signature will be inserted in front of this one when executing.
+    //
+    // The signatures are positional, so the operator key is the exception: the operator
+    // signs at the payer position when executing, and a signature of its key added here is
+    // for the keys of the entity (e.g. a file keyed by the operator key), after the payer.
+    fn push_signature(&mut self, signature: &Signature, operator_signs: bool) {
+        if self.has_signature(signature) {
+            return;
+        }
+
+        let index = self.signatures().len() + usize::from(operator_signs);
+
+        // note: these cannot fail
+        let signature = match signature_shape(self.tx.get_body(), index) {
+            SignatureShape::Key => signature.to_proto().unwrap(),
+            SignatureShape::KeyList => (&[signature][..]).to_proto().unwrap(),
+        };
+
+        self.signatures().push(signature);
+    }
+
+    // Sign as the operator, which pays for the transaction, when executing
+    fn sign_as_operator(mut self, secret: Option<&OperatorSecret>) -> Result<Self, Error> {
+        let tx = &mut self.tx;
+
+        // note: cannot fail
+        let id = tx
+            .get_body()
+            .transactionID
+            .as_ref()
+            .unwrap()
+            .clone();
+
+        let operator = id.accountID.as_ref().unwrap().clone();
+
+        if !tx.has_sigs() {
+            // If .sign was never called this will be still need to be initialized
+            tx.set_sigs(proto::BasicTypes::SignatureList::new());
+        }
+
+        if let Some(secret) = secret {
+            let signature = secret()?.sign(&self.bytes);
+
+            // the operator pays, so its signature is shaped as that of the payer
+            let signature = match signature_shape(tx.get_body(), 0) {
+                SignatureShape::Key => signature.to_proto()?,
+                SignatureShape::KeyList => (&[&signature][..]).to_proto()?,
+            };
+
+            match &tx.get_body().clone().data {
+                Some(cryptoTransfer(data)) => {
+                    // Insert a signature for the operator if the operator
+                    // is sending any monies
+                    for transfer in &data.transfers.as_ref().unwrap().accountAmounts {
+                        if transfer.accountID.as_ref().unwrap() == &operator {
+                            tx.sigs.as_mut().unwrap().sigs.push(signature.clone());
+                        }
+                    }
+                }
+
+                _ => {}
+            }
+
+            // Sign as the operator of the transaction
+            tx.sigs.as_mut().unwrap().sigs.insert(0, signature);
+        }
+
+        match tx.mut_body().data {
+            Some(cryptoDelete(ref mut data)) => {
+                if !data.has_transferAccountID() {
+                    // default the transfer account ID to the operator of the transaction
+                    data.set_transferAccountID(operator);
+                }
+            }
+
+            _ => {}
+        }
+
+        Ok(self)
+    }
+}
+
 enum TransactionKind<T> {
     Empty,
     Err(Error),
@@ -75,10 +221,24 @@ impl<T> TransactionKind<T> {
 }
 
 pub struct Transaction<T, S = TransactionBuilder<T>> {
-    crypto_service: Arc<CryptoServiceClient>,
-    file_service: Arc<FileServiceClient>,
-    contract_service: Arc<SmartContractServiceClient>,
-    secret: Option<Arc<dyn Fn() -> Result<SecretKey, Error> + Send + Sync>>,
+    network: Arc<Network>,
+    secret: Option<OperatorSecret>,
+    on_retry: Option<OnRetry>,
+    tracer: Option<Tracer>,
+    regenerate_id: bool,
+    budget: Option<Budget>,
+    clock: SharedClock,
+    last_node: Option<AccountId>,
+    tags: Vec<(String, String)>,
+    ignored: Vec<&'static str>,
+
+    // checked when executing a transaction loaded with `from_bytes`; the fee of a built
+    // transaction went through the policy of its builder already
+    fee_policy: Option<FeePolicy>,
+
+    // when `build` turned the builder into a body, for the trace
+    built_at: Option<Instant>,
+
     kind: TransactionKind<T>,
     phantom: PhantomData<S>,
 }
@@ -89,16 +249,31 @@ impl<T: 'static> Transaction<T, TransactionBuilder<T>> {
         T: Object + ToProto<proto::TransactionBody::TransactionBody_oneof_data> + 'static,
     {
         Self {
-            crypto_service: client.crypto.clone(),
-            file_service: client.file.clone(),
-            contract_service: client.contract.clone(),
+            network: client.network.clone(),
             secret: client.operator_secret.clone(),
+            on_retry: None,
+            tracer: None,
+            regenerate_id: false,
+            budget: client.budget.clone(),
+            clock: client.clock.clone(),
+            last_node: None,
+            tags: Vec::new(),
+            ignored: Vec::new(),
+            fee_policy: None,
+            built_at: None,
             kind: TransactionKind::Builder(TransactionBuilder {
-                id: client.operator.map(TransactionId::new),
-                node: client.node,
+                id: client
+                    .operator
+                    .map(|id| TransactionId::generated_at(id, client.clock.now())),
+                node: None,
+                node_candidates: Vec::new(),
+                default_node: Some(client.default_node()),
+                routes: client.routes.clone(),
                 memo: None,
                 inner: Box::<T>::new(inner) as Box<dyn Object>,
                 fee: 100_300_000,
+                fee_policy: client.fee_policy.clone(),
+                shard_realm: (client.shard, client.realm),
                 generate_record: false,
                 phantom: PhantomData,
             }),
@@ -106,8 +281,8 @@ impl<T: 'static> Transaction<T, TransactionBuilder<T>> {
         }
     }
 
-    pub fn memo(&mut self, memo: impl Into<String>) -> &mut Self {
-        if let Some(state) = self.as_builder() {
+    pub fn memo(&mut self, memo: impl Into<Memo>) -> &mut Self {
+        if let Some(state) = self.as_builder("memo") {
             state.memo = Some(memo.into());
         }
 
@@ -118,24 +293,59 @@ impl<T: 'static> Transaction<T, TransactionBuilder<T>> {
         // This resets any default operator we may have had
         self.secret = None;
 
-        if let Some(state) = self.as_builder() {
-            state.id = Some(TransactionId::new(id));
+        let now = self.clock.now();
+
+        if let Some(state) = self.as_builder("operator") {
+            state.id = Some(TransactionId::generated_at(id, now));
+        }
+
+        self
+    }
+
+    /// Pay for this transaction from the account `id`, signing as the payer with `secret`
+    /// instead of the client operator.
+    ///
+    /// The transaction ID is generated from the payer account. Any other signatures the
+    /// transaction requires (e.g. the keys of the accounts being debited) are still
+    /// added with `sign`.
+    pub fn payer(&mut self, id: AccountId, secret: SecretKey) -> &mut Self {
+        self.secret = Some(Arc::new(move || Ok(secret.clone())));
+
+        let now = self.clock.now();
+
+        if let Some(state) = self.as_builder("payer") {
+            state.id = Some(TransactionId::generated_at(id, now));
         }
 
         self
     }
 
     pub fn node(&mut self, id: AccountId) -> &mut Self {
-        if let Some(state) = self.as_builder() {
+        if let Some(state) = self.as_builder("node") {
             state.node = Some(id);
         }
 
         self
     }
 
+    /// Send the transaction to one of `nodes`, picked when the transaction is built: the
+    /// first that has not been failing recent requests.
+    ///
+    /// The node that was picked is in the body of the transaction, and is returned by
+    /// `last_node` once the transaction is executed. To try another node after a failure,
+    /// build the transaction again with the same candidates. A node set with `node` takes
+    /// precedence.
+    pub fn node_any_of(&mut self, nodes: &[AccountId]) -> &mut Self {
+        if let Some(state) = self.as_builder("node_any_of") {
+            state.node_candidates = nodes.to_vec();
+        }
+
+        self
+    }
+
     /// The fee the client pays, which is split between the network and the node.
     pub fn fee(&mut self, fee: u64) -> &mut Self {
-        if let Some(state) = self.as_builder() {
+        if let Some(state) = self.as_builder("fee") {
             state.fee = fee;
         }
 
@@ -145,7 +355,7 @@ impl<T: 'static> Transaction<T, TransactionBuilder<T>> {
     /// Should a record of this transaction be generated?
     /// A receipt is always generated, but the record is optional.
     pub fn generate_record(&mut self, generate: bool) -> &mut Self {
-        if let Some(state) = self.as_builder() {
+        if let Some(state) = self.as_builder("generate_record") {
             state.generate_record = generate;
         }
 
@@ -160,14 +370,51 @@ impl<T: 'static> Transaction<T, TransactionBuilder<T>> {
         self.build().execute_async()
     }
 
+    /// Execute the transaction, recording when each step happened.
+    pub fn execute_traced(&mut self) -> (Result<TransactionId, Error>, ExecutionTrace) {
+        self.build().execute_traced()
+    }
+
+    /// A one-line human-readable description of the transaction, e.g.
+    /// `CryptoTransfer: 0:0:2 -100 ℏ, 0:0:1001 +100 ℏ, fee ≤ 1.003 ℏ, node 0:0:3, id ...`.
+    ///
+    /// This builds the transaction; it can only be signed afterwards.
+    pub fn summary(&mut self) -> Result<String, Error> {
+        self.build().summary()
+    }
+
+    /// Build the transaction so that it can no longer be changed, only signed.
+    pub fn freeze(mut self) -> Transaction<T, TransactionRaw> {
+        self.build();
+
+        Transaction {
+            network: self.network,
+            secret: self.secret,
+            on_retry: self.on_retry,
+            tracer: self.tracer,
+            regenerate_id: self.regenerate_id,
+            budget: self.budget,
+            clock: self.clock,
+            last_node: self.last_node,
+            tags: self.tags,
+            ignored: self.ignored,
+            fee_policy: self.fee_policy,
+            built_at: self.built_at,
+            kind: self.kind,
+            phantom: PhantomData,
+        }
+    }
+
     pub fn execute(&mut self) -> Result<TransactionId, Error> {
         crate::RUNTIME
             .lock()
             .block_on(self.execute_async())
     }
 
+    // `setter` is the name of the method asking; it is reported when the transaction is
+    // executed if the call had to be ignored because the transaction failed to build
     #[inline]
-    fn as_builder(&mut self) -> Option<&mut TransactionBuilder<T>> {
+    fn as_builder(&mut self, setter: &'static str) -> Option<&mut TransactionBuilder<T>> {
         match &mut self.kind {
             TransactionKind::Builder(ref mut state) => Some(state),
 
@@ -177,7 +424,7 @@ impl<T: 'static> Transaction<T, TransactionBuilder<T>> {
             }
 
             TransactionKind::Err(_) => {
-                // should never be able to happen (in Rust)
+                self.ignored.push(setter);
                 None
             }
 
@@ -188,17 +435,59 @@ impl<T: 'static> Transaction<T, TransactionBuilder<T>> {
         }
     }
 
+    // The builder state, unless the transaction failed to build
+    fn builder_ref(&self) -> Option<&TransactionBuilder<T>> {
+        match &self.kind {
+            TransactionKind::Builder(state) => Some(state),
+            _ => None,
+        }
+    }
+
+    /// The memo set so far, for showing the transaction before it is built.
+    pub fn get_memo(&self) -> Option<&Memo> {
+        self.builder_ref()?.memo.as_ref()
+    }
+
+    /// The fee set so far, before the fee policy of the client is applied.
+    pub fn get_fee(&self) -> Option<u64> {
+        Some(self.builder_ref()?.fee)
+    }
+
+    /// The node the transaction will be sent to: the one set with `node` (or picked from
+    /// `node_any_of`), or else the one the client routes this kind of transaction to, or else
+    /// the default node.
+    pub fn get_node(&self) -> Option<AccountId> {
+        let state = self.builder_ref()?;
+        let picked = self.network.pick_node(&state.node_candidates);
+
+        let routed = state
+            .inner
+            .query_ref::<dyn ToProto<proto::TransactionBody::TransactionBody_oneof_data>>()
+            .and_then(|inner| inner.to_proto().ok())
+            .and_then(|data| state.routes.transaction_node(&data));
+
+        state.node.or(picked).or(routed).or(state.default_node)
+    }
+
     #[inline]
-    pub(crate) fn inner(&mut self) -> &mut T {
-        // not possible to fail in safe rust
+    pub(crate) fn inner_ref(&self) -> Option<&T> {
+        self.builder_ref()?
+            .inner
+            .query_ref::<dyn Any>()
+            .and_then(|inner| inner.downcast_ref())
+    }
+
+    #[inline]
+    // The state of the kind of transaction, for its setter named `setter`; `None` (with the
+    // call recorded as ignored) if the transaction failed to build
+    pub(crate) fn inner(&mut self, setter: &'static str) -> Option<&mut T> {
         match self
-            .as_builder()
-            .unwrap()
+            .as_builder(setter)?
             .inner
             .query_mut::<dyn Any>()
             .and_then(|inner| inner.downcast_mut())
         {
-            Some(inner) => inner,
+            Some(inner) => Some(inner),
 
             // not possible in safe rust to get here
             _ => unreachable!(),
@@ -226,161 +515,511 @@ impl<T: 'static> Transaction<T, TransactionRaw> {
         }
     }
 
+    /// Sign the transaction with `secret`. Signing twice with the same key has no effect.
+    ///
+    /// The operator signs as the payer when executing; sign with its key here as well when
+    /// it is also a key of the entity, e.g. of a file created with `operator_public_key`.
     pub fn sign(&mut self, secret: &SecretKey) -> &mut Self {
+        let operator_signs = self.secret.is_some();
+
         if let Some(state) = self.as_raw() {
-            // note: this cannot fail
+            let signature = secret.sign(&state.bytes);
+            state.push_signature(&signature, operator_signs);
+        }
 
-            let id = state
-                .tx
-                .get_body()
-                .transactionID
-                .as_ref()
-                .unwrap()
-                .clone();
+        self
+    }
 
-            // note: this cannot fail
-            let operator = id.accountID.as_ref().unwrap().clone();
+    /// The serialized body of the transaction; this is what each signature is made over.
+    ///
+    /// Returns `None` if the transaction failed to build.
+    pub fn body_bytes(&self) -> Option<&[u8]> {
+        match &self.kind {
+            TransactionKind::Raw(state) => Some(&state.bytes),
+            _ => None,
+        }
+    }
 
-            // HACK: If an accountNum is < 1000 pretend it has a slightly more complex key structure
-            let signature = if operator.get_accountNum() < 1000 {
-                (&[&secret.sign(&state.bytes)][..]).to_proto().unwrap()
-            } else {
-                secret.sign(&state.bytes).to_proto().unwrap()
-            };
+    /// Add a signature made elsewhere (e.g. on another machine) over `body_bytes()`.
+    ///
+    /// The signature is verified against `public` before it is added.
+    pub fn add_signature(
+        &mut self,
+        public: &PublicKey,
+        signature: &Signature,
+    ) -> Result<&mut Self, Error> {
+        let operator_signs = self.secret.is_some();
 
-            if !state.tx.has_sigs() {
-                state.tx.set_sigs(proto::BasicTypes::SignatureList::new());
+        if let Some(state) = self.as_raw() {
+            if !public.verify(&state.bytes, signature)? {
+                Err(ErrorKind::InvalidSignature(public.clone()))?;
             }
 
-            // note: this cannot fail
-            let signatures = &mut state.tx.sigs.as_mut().unwrap().sigs;
-            signatures.push(signature);
+            state.push_signature(signature, operator_signs);
         }
 
-        self
+        Ok(self)
+    }
+
+    /// A one-line human-readable description of the transaction, e.g. for a confirmation
+    /// prompt before signing.
+    pub fn summary(&mut self) -> Result<String, Error> {
+        match self.as_raw() {
+            Some(state) => Ok(summary::summarize(state.tx.get_body())),
+
+            // the transaction failed to build; take the error out to report it
+            None => Err(self.take_raw().err().unwrap()),
+        }
+    }
+
+    /// Serialize the transaction, including any signatures added so far.
+    pub fn to_bytes(&mut self) -> Result<Vec<u8>, Error> {
+        match self.as_raw() {
+            Some(state) => Ok(state.tx.write_to_bytes()?),
+
+            // the transaction failed to build; take the error out to report it
+            None => Err(self.take_raw().err().unwrap()),
+        }
+    }
+
+    /// Load a transaction serialized with `to_bytes` so it can be signed and executed
+    /// through `client`. The operator of `client` does not sign a loaded transaction.
+    pub fn from_bytes(client: &Client, bytes: impl AsRef<[u8]>) -> Result<Self, Error> {
+        let tx: proto::Transaction::Transaction = protobuf::parse_from_bytes(bytes.as_ref())?;
+
+        if !tx.has_body() {
+            Err(ErrorKind::MissingField("body"))?;
+        }
+
+        let bytes = tx.get_body().write_to_bytes()?;
+
+        Ok(Self {
+            network: client.network.clone(),
+            secret: None,
+            on_retry: None,
+            tracer: None,
+            regenerate_id: false,
+            budget: client.budget.clone(),
+            clock: client.clock.clone(),
+            last_node: None,
+            tags: Vec::new(),
+            ignored: Vec::new(),
+            fee_policy: client.fee_policy.clone(),
+            built_at: None,
+            kind: TransactionKind::Raw(TransactionRaw { tx, bytes }),
+            phantom: PhantomData,
+        })
+    }
+
+    // Add a signature that has already been arranged to match a complex key structure
+    pub(crate) fn push_signature_proto(&mut self, signature: proto::BasicTypes::Signature) {
+        if let Some(state) = self.as_raw() {
+            state.signatures().push(signature);
+        }
     }
 
+    /// Submit the transaction to its node and return its ID once the node accepts it.
+    ///
+    /// A node that responds `Busy` is sent the same transaction again, up to 5 times with a
+    /// growing delay in between (reported to `on_retry`), before its `Busy` is returned.
     pub fn execute(&mut self) -> Result<TransactionId, Error> {
         crate::RUNTIME
             .lock()
             .block_on(self.execute_async())
     }
 
+    /// Execute the transaction, recording when each step happened.
+    pub fn execute_traced(&mut self) -> (Result<TransactionId, Error>, ExecutionTrace) {
+        crate::RUNTIME
+            .lock()
+            .block_on(self.execute_traced_async())
+    }
+
+    pub fn execute_traced_async(
+        &mut self,
+    ) -> impl Future<Output = (Result<TransactionId, Error>, ExecutionTrace)> {
+        let mut trace = ExecutionTrace::new();
+        trace.tags = self.tags.clone();
+
+        // the body was built before execution, possibly long before
+        if let Some(at) = self.built_at {
+            trace.events.push(TraceEvent {
+                at,
+                step: TraceStep::Build,
+            });
+        }
+
+        let tracer = Arc::new(Mutex::new(trace));
+
+        self.tracer = Some(tracer.clone());
+
+        let execution = self.execute_async();
+        self.tracer = None;
+
+        async move {
+            let result = execution.await;
+            let trace = tracer.lock().clone();
+
+            (result, trace)
+        }
+    }
+
     pub fn execute_async(&mut self) -> impl Future<Output = Result<TransactionId, Error>> {
-        let crypto = self.crypto_service.clone();
-        let file = self.file_service.clone();
-        let contract = self.contract_service.clone();
+        let network = self.network.clone();
+        let on_retry = self.on_retry.clone();
+        let tracer = self.tracer.clone();
+        let budget = self.budget.clone();
+        let clock = self.clock.clone();
+        let fee_policy = self.fee_policy.clone();
+
+        // keep an unsigned copy to sign again under a new ID if the valid window passes
+        let secret = self.secret.clone().filter(|_| self.regenerate_id);
+        let unsigned = match self.as_raw() {
+            Some(state) if secret.is_some() && state.signatures().is_empty() => {
+                Some(state.tx.clone())
+            }
+
+            _ => None,
+        };
+
         let state = self.take_raw();
 
+        self.last_node = state
+            .as_ref()
+            .ok()
+            .map(|state| state.tx.get_body().get_nodeAccountID().clone().into());
+
         async move {
             let state = state?;
+            trace::record(&tracer, TraceStep::Sign);
 
             let mut tx = state.tx;
-            let id = tx
+            let fee = tx.get_body().get_transactionFee();
+            fee_policy::check(fee_policy.as_ref(), summary::kind_name(tx.get_body()), fee)?;
+
+            if let Some(budget) = &budget {
+                budget.spend_fee(fee)?;
+            }
+
+            let mut id = tx
                 .get_body()
                 .transactionID
                 .as_ref()
                 .unwrap()
                 .clone();
 
-            log::trace!(target: "hedera::transaction", "sent: {:#?}", tx);
-
-            let o = grpc::RequestOptions::default();
-            let response = match tx.mut_body().data {
-                //////////////////////// CRYPTO TRANSACTIONS
-                Some(cryptoCreateAccount(_)) => crypto.create_account(o, tx),
-                Some(cryptoUpdateAccount(_)) => crypto.update_account(o, tx),
-                Some(cryptoTransfer(_)) => crypto.crypto_transfer(o, tx),
-                Some(cryptoDeleteClaim(_)) => crypto.delete_claim(o, tx),
-                Some(cryptoDelete(_)) => crypto.crypto_delete(o, tx),
-                //////////////////////// FILE TRANSACTIONS
-                Some(fileCreate(_)) => file.create_file(o, tx),
-                Some(fileAppend(_)) => file.append_content(o, tx),
-                //////////////////////// CONTRACT TRANSACTIONS
-                Some(contractCreateInstance(_)) => contract.create_contract(o, tx),
-                Some(contractUpdateInstance(_)) => contract.update_contract(o, tx),
-                Some(contractDeleteInstance(_)) => contract.delete_contract(o, tx),
-                Some(contractCall(_)) => contract.contract_call_method(o, tx),
-
-                _ => unimplemented!(),
+            let node: AccountId = tx.get_body().get_nodeAccountID().clone().into();
+            let _request = network.start_request()?;
+            let mut channel = network.channel(Some(node))?;
+            let mut reconnects = 0;
+
+            let kind = summary::kind_name(tx.get_body());
+
+            payload_log::sent(
+                network.payload_logging,
+                "hedera::transaction",
+                node,
+                kind,
+                Some(&id.clone().into()),
+                &tx,
+            );
+
+            // admin deletes and recovers are sent to the service of the entity they act on
+            let admin_contract = match tx.get_body().data {
+                Some(systemDelete(ref data)) => data.has_contractID(),
+                Some(systemUndelete(ref data)) => data.has_contractID(),
+                _ => false,
             };
 
-            let response = Compat01As03::new(response.drop_metadata()).await?;
-            log::trace!("recv: {:#?}", response);
+            let mut attempt = 0;
+
+            // set once an attempt failed in a way that leaves it unknown whether the node got
+            // the transaction; it must then not be sent again under a new ID
+            let mut possibly_submitted = false;
+
+            loop {
+                if (attempt > 0 || reconnects > 0) && valid_window_elapsed(&tx, clock.now()) {
+                    match (&unsigned, &secret) {
+                        (Some(unsigned), Some(secret)) if !possibly_submitted => {
+                            tx = regenerate_id(unsigned, secret, clock.now())?.tx;
+                            id = tx.get_body().get_transactionID().clone();
+                            trace::record(&tracer, TraceStep::Sign);
+                        }
+
+                        _ => Err(ErrorKind::ValidWindowElapsed(id.into()))?,
+                    }
+                }
+
+                if let Some(budget) = &budget {
+                    budget.spend_attempt()?;
+                }
 
-            try_precheck!(response).map(|_| id.into())
+                trace::record(&tracer, TraceStep::Submit { attempt, node });
+
+                let mut tx = tx.clone();
+                let (crypto, file, contract) = (&channel.crypto, &channel.file, &channel.contract);
+                let freeze_service = &channel.freeze;
+                let o = grpc::RequestOptions::default();
+                let sent = Instant::now();
+                let response = match tx.mut_body().data {
+                    //////////////////////// CRYPTO TRANSACTIONS
+                    Some(cryptoCreateAccount(_)) => crypto.create_account(o, tx),
+                    Some(cryptoUpdateAccount(_)) => crypto.update_account(o, tx),
+                    Some(cryptoTransfer(_)) => crypto.crypto_transfer(o, tx),
+                    Some(cryptoAddClaim(_)) => crypto.add_claim(o, tx),
+                    Some(cryptoDeleteClaim(_)) => crypto.delete_claim(o, tx),
+                    Some(cryptoDelete(_)) => crypto.crypto_delete(o, tx),
+                    //////////////////////// FILE TRANSACTIONS
+                    Some(fileCreate(_)) => file.create_file(o, tx),
+                    Some(fileAppend(_)) => file.append_content(o, tx),
+                    Some(fileUpdate(_)) => file.update_file(o, tx),
+                    Some(fileDelete(_)) => file.delete_file(o, tx),
+                    //////////////////////// CONTRACT TRANSACTIONS
+                    Some(contractCreateInstance(_)) => contract.create_contract(o, tx),
+                    Some(contractUpdateInstance(_)) => contract.update_contract(o, tx),
+                    Some(contractDeleteInstance(_)) => contract.delete_contract(o, tx),
+                    Some(contractCall(_)) => contract.contract_call_method(o, tx),
+                    //////////////////////// ADMIN TRANSACTIONS
+                    Some(systemDelete(_)) if admin_contract => contract.system_delete(o, tx),
+                    Some(systemDelete(_)) => file.system_delete(o, tx),
+                    Some(systemUndelete(_)) if admin_contract => contract.system_undelete(o, tx),
+                    Some(systemUndelete(_)) => file.system_undelete(o, tx),
+                    Some(freeze(_)) => freeze_service.freeze(o, tx),
+
+                    _ => Err(ErrorKind::UnsupportedTransactionKind(kind))?,
+                };
+
+                let response = match Compat01As03::new(response.drop_metadata()).await {
+                    Ok(response) => response,
+
+                    Err(ref error)
+                        if retry::is_connection_error(error)
+                            && reconnects < retry::MAX_RECONNECTS =>
+                    {
+                        network.record(Some(node), Outcome::Failed, sent.elapsed());
+                        possibly_submitted |= !retry::is_not_sent(error);
+                        reconnects += 1;
+                        trace::record(&tracer, TraceStep::Reconnect);
+                        channel = network.reconnect(Some(node), &channel)?;
+                        continue;
+                    }
+
+                    Err(error) => {
+                        log::warn!(
+                            target: "hedera::transaction",
+                            "{} did not respond: {}",
+                            node,
+                            error
+                        );
+
+                        network.record(Some(node), Outcome::Failed, sent.elapsed());
+                        Err(retry::transport_error(error))?
+                    }
+                };
+
+                payload_log::received(
+                    network.payload_logging,
+                    "hedera::transaction",
+                    node,
+                    kind,
+                    Some(&id.clone().into()),
+                    &response,
+                );
+
+                let status = response.get_nodeTransactionPrecheckCode().into();
+                trace::record(&tracer, TraceStep::Response { status });
+
+                let outcome = match status {
+                    Status::Busy => Outcome::Busy,
+                    _ => Outcome::Response,
+                };
+
+                network.record(Some(node), outcome, sent.elapsed());
+                record_stats(&network, status, fee);
+
+                match status {
+                    Status::Busy if attempt < retry::MAX_ATTEMPTS => {
+                        network.stats.lock().record_retry();
+                        attempt += 1;
+                        let delay = retry::backoff(attempt);
+
+                        log::debug!(
+                            target: "hedera::transaction",
+                            "{} is busy; retrying in {:?}",
+                            node,
+                            delay
+                        );
+
+                        trace::record(
+                            &tracer,
+                            TraceStep::Retry {
+                                attempt,
+                                status,
+                                delay,
+                            },
+                        );
+
+                        if let Some(on_retry) = &on_retry {
+                            on_retry(attempt, Status::Busy, delay);
+                        }
+
+                        clock.sleep(delay).await;
+                    }
+
+                    Status::InsufficientTxFee => Err(ErrorKind::InsufficientFee {
+                        offered: fee,
+                        required: None,
+                    })?,
+
+                    _ if accepted_earlier(status, possibly_submitted) => {
+                        log::debug!(
+                            target: "hedera::transaction",
+                            "{} already has the copy sent before reconnecting",
+                            node
+                        );
+
+                        break Ok(id.into());
+                    }
+
+                    _ => break try_precheck!(response).map(|_| id.into()),
+                }
+            }
         }
     }
 }
 
+// Count the pre-check `status` of a response, and the maximum `fee` if the node accepted
+// the transaction
+fn record_stats(network: &Network, status: Status, fee: u64) {
+    let mut stats = network.stats.lock();
+    stats.record_precheck(status);
+
+    if status == Status::Ok {
+        stats.record_fee(fee);
+    }
+}
+
+// Does `status`, in response to sending the transaction again, mean that the node already
+// accepted a copy that was sent before the connection broke?
+fn accepted_earlier(status: Status, possibly_submitted: bool) -> bool {
+    possibly_submitted && status == Status::DuplicateTransaction
+}
+
+// Has the window in which the network accepts the transaction passed by `now`?
+fn valid_window_elapsed(tx: &proto::Transaction::Transaction, now: DateTime<Utc>) -> bool {
+    let body = tx.get_body();
+    let valid_start: DateTime<Utc> = body
+        .get_transactionID()
+        .get_transactionValidStart()
+        .clone()
+        .into();
+    let valid_duration = body.get_transactionValidDuration().get_seconds();
+
+    now >= valid_start + chrono::Duration::seconds(valid_duration)
+}
+
+// Sign `unsigned` again as the operator under a new transaction ID generated at `now`
+fn regenerate_id(
+    unsigned: &proto::Transaction::Transaction,
+    secret: &OperatorSecret,
+    now: DateTime<Utc>,
+) -> Result<TransactionRaw, Error> {
+    let mut tx = unsigned.clone();
+    let payer = tx.get_body().get_transactionID().get_accountID().clone();
+
+    tx.mut_body()
+        .set_transactionID(TransactionId::generated_at(payer.into(), now).to_proto()?);
+
+    let bytes = tx.get_body().write_to_bytes()?;
+
+    TransactionRaw { bytes, tx }.sign_as_operator(Some(secret))
+}
+
 impl<T: 'static, S: 'static> Transaction<T, S> {
-    #[inline]
-    pub(crate) fn take_raw(&mut self) -> Result<TransactionRaw, Error> {
-//        use self::proto::Transaction::Transaction_oneof_bodyData::*;
+    /// Call `callback` each time this transaction is re-submitted after a `Busy` response,
+    /// with the attempt number, the response status and the delay before the next attempt.
+    pub fn on_retry(
+        &mut self,
+        callback: impl Fn(usize, Status, Duration) + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.on_retry = Some(Arc::new(callback));
+        self
+    }
 
-        match self.kind.take() {
-            TransactionKind::Builder(_) => self.build().take_raw(),
+    /// Sign the transaction again under a new transaction ID, rather than fail with
+    /// `ErrorKind::ValidWindowElapsed`, if its valid window passes while it is being retried.
+    ///
+    /// This only applies when the operator is the only signer; a transaction with other
+    /// signatures cannot be signed again without their keys. It is also not done once an
+    /// attempt may have reached the node, since the transaction could then execute twice.
+    pub fn regenerate_expired_id(&mut self, regenerate: bool) -> &mut Self {
+        self.regenerate_id = regenerate;
+        self
+    }
 
-            TransactionKind::Raw(mut state) => {
-                let tx = &mut state.tx;
+    /// Count the attempts and maximum fee of this transaction against `budget`,
+    /// instead of any budget of the client.
+    pub fn budget(&mut self, budget: &Budget) -> &mut Self {
+        self.budget = Some(budget.clone());
+        self
+    }
 
-                // note: cannot fail
-                let id = tx
-                    .get_body()
-                    .transactionID
-                    .as_ref()
-                    .unwrap()
-                    .clone();
+    /// Attach `value` under `key` to this transaction, e.g. the ID of the order or job it is
+    /// for, replacing any value already under `key`.
+    ///
+    /// Tags are kept by the client and never sent to the network. They are in the
+    /// `ExecutionTrace` of `execute_traced`, to tie the outcome back to what asked for it.
+    pub fn tag(&mut self, key: impl Into<String>, value: impl Into<String>) -> &mut Self {
+        let (key, value) = (key.into(), value.into());
+
+        match self.tags.iter_mut().find(|(existing, _)| *existing == key) {
+            Some(tag) => tag.1 = value,
+            None => self.tags.push((key, value)),
+        }
 
-                let operator = id.accountID.as_ref().unwrap().clone();
+        self
+    }
 
-                if !tx.has_sigs() {
-                    // If .sign was never called this will be still need to be initialized
-                    tx.set_sigs(proto::BasicTypes::SignatureList::new());
-                }
+    /// The tags attached with `tag`, in the order they were first set.
+    pub fn tags(&self) -> &[(String, String)] {
+        &self.tags
+    }
 
-                if let Some(secret) = &self.secret {
-                    // HACK: If an accountNum is < 1000 pretend it has a slightly more complex key structure
-                    let signature = if operator.get_accountNum() < 1000 {
-                        (&[&secret()?.sign(&state.bytes)][..]).to_proto().unwrap()
-                    } else {
-                        secret()?.sign(&state.bytes).to_proto().unwrap()
-                    };
-
-                    match &tx.get_body().clone().data {
-                        Some(cryptoTransfer(data)) => {
-                            // Insert a signature for the operator if the operator
-                            // is sending any monies
-                            for transfer in &data.transfers.as_ref().unwrap().accountAmounts {
-                                if transfer.accountID.as_ref().unwrap() == &operator {
-                                    tx.sigs.as_mut().unwrap().sigs.push(signature.clone());
-                                }
-                            }
-                        }
+    /// Do not sign with the operator secret of the client when executing.
+    ///
+    /// For relaying a transaction whose payer signature is attached elsewhere, with
+    /// `add_signature` or `SignatureCollector`.
+    pub fn skip_operator_signature(&mut self) -> &mut Self {
+        self.secret = None;
+        self
+    }
 
-                        _ => {}
-                    }
+    /// The node the transaction was sent to, once it has been executed; e.g. to tell which
+    /// node to look at when a transaction fails.
+    pub fn last_node(&self) -> Option<AccountId> {
+        self.last_node
+    }
 
-                    // Sign as the operator of the transaction
-                    tx.sigs.as_mut().unwrap().sigs.insert(0, signature);
-                }
+    #[inline]
+    pub(crate) fn take_raw(&mut self) -> Result<TransactionRaw, Error> {
+//        use self::proto::Transaction::Transaction_oneof_bodyData::*;
 
-                match tx.mut_body().data {
-                    Some(cryptoDelete(ref mut data)) => {
-                        if !data.has_transferAccountID() {
-                            // default the transfer account ID to the operator of the transaction
-                            data.set_transferAccountID(operator);
-                        }
-                    }
+        match self.kind.take() {
+            TransactionKind::Builder(_) => self.build().take_raw(),
 
-                    _ => {}
-                }
+            TransactionKind::Raw(state) => state.sign_as_operator(self.secret.as_ref()),
 
-                Ok(state)
+            TransactionKind::Err(err) => {
+                if self.ignored.is_empty() {
+                    Err(err)
+                } else {
+                    Err(ErrorKind::IgnoredSetters {
+                        setters: self.ignored.join(", "),
+                        cause: err.to_string(),
+                    })?
+                }
             }
 
-            TransactionKind::Err(err) => Err(err),
-
             TransactionKind::Empty => panic!("transaction already executed"),
         }
     }
@@ -403,14 +1042,19 @@ impl<T: 'static, S: 'static> Transaction<T, S> {
             }
         }
 
-        if let TransactionKind::Builder(state) = self.kind.take() {
+        if let TransactionKind::Builder(mut state) = self.kind.take() {
+            if state.node.is_none() {
+                state.node = self.network.pick_node(&state.node_candidates);
+            }
+
             match state.to_proto() {
                 Ok(tx) => {
                     // note: this cannot fail
                     let tx: proto::Transaction::Transaction = tx;
                     let bytes = tx.get_body().write_to_bytes().unwrap();
 
-                    self.kind = TransactionKind::Raw(TransactionRaw { tx, bytes })
+                    self.kind = TransactionKind::Raw(TransactionRaw { tx, bytes });
+                    self.built_at = Some(Instant::now());
                 }
 
                 Err(error) => {
@@ -450,20 +1094,96 @@ impl<T> ToProto<proto::TransactionBody::TransactionBody> for TransactionBuilder<
             .ok_or_else(|| ErrorKind::MissingField("operator"))?;
 
         let mut body = proto::TransactionBody::TransactionBody::new();
-        let node = self.node.ok_or_else(|| ErrorKind::MissingField("node"))?;
+        let data = inner.to_proto()?;
+
+        let node = self
+            .node
+            .or_else(|| self.routes.transaction_node(&data))
+            .or(self.default_node)
+            .ok_or_else(|| ErrorKind::MissingField("node"))?;
 
         body.set_nodeAccountID(node.to_proto()?);
-        body.set_transactionValidDuration(Duration::from_secs(120).to_proto()?);
-        body.set_transactionFee(self.fee);
+        body.set_transactionValidDuration(VALID_DURATION.to_proto()?);
         body.set_generateRecord(self.generate_record);
         body.set_transactionID(tx_id.to_proto()?);
-        body.data = Some(inner.to_proto()?);
-        body.set_memo(if let Some(memo) = &self.memo {
-            memo.to_owned()
-        } else {
-            String::new()
-        });
+        body.data = Some(data);
+
+        let (shard, realm) = self.shard_realm;
+        shard_realm::check(&body, shard, realm)?;
+
+        let kind = summary::kind_name(&body);
+        body.set_transactionFee(fee_policy::apply(self.fee_policy.as_ref(), kind, self.fee)?);
+
+        if let Some(memo) = &self.memo {
+            memo.check_len()?;
+            body.set_memo(memo.as_str().to_owned());
+        }
 
         Ok(body)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::accepted_earlier;
+    use crate::{AccountId, Client, ErrorKind, SecretKey, Status};
+    use failure::Error;
+
+    #[test]
+    fn test_duplicate_after_reconnect_is_accepted() {
+        assert!(accepted_earlier(Status::DuplicateTransaction, true));
+
+        // without an earlier copy that may have arrived, a duplicate is the caller's mistake
+        assert!(!accepted_earlier(Status::DuplicateTransaction, false));
+        assert!(!accepted_earlier(Status::InvalidSignature, true));
+    }
+
+    #[test]
+    fn test_operator_signs_for_its_own_file() -> Result<(), Error> {
+        let (operator, _) = SecretKey::generate("");
+        let operator_secret = operator.clone();
+
+        let client = Client::builder()
+            .node("0.0.3@127.0.0.1:50211".parse()?)
+            .operator(AccountId::new(0, 0, 1001), move || operator_secret.clone())
+            .build()?;
+
+        let mut tx = client.create_file();
+        tx.key(operator.public()).contents(b"hello".to_vec());
+
+        // signing again with the same key has no effect
+        let state = tx.sign(&operator).sign(&operator).take_raw()?;
+
+        // the payer signature, and the signature for the `KeyList` of the file
+        let signatures = state.tx.get_sigs().get_sigs();
+        assert_eq!(signatures.len(), 2);
+        assert!(signatures[0].has_ed25519());
+        assert_eq!(signatures[1].get_signatureList().get_sigs().len(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_setters_after_failed_build_are_ignored() -> Result<(), Error> {
+        let client = Client::builder()
+            .node("0.0.3@127.0.0.1:50211".parse()?)
+            .operator(AccountId::new(0, 0, 1001), || SecretKey::generate("").0)
+            .build()?;
+
+        let mut tx = client.create_account();
+        tx.memo("x".repeat(101)).sign(&SecretKey::generate("").0);
+
+        // setters of the kind of transaction are recorded, not a panic
+        tx.initial_balance(100).memo("ok");
+
+        match tx.take_raw().err().unwrap().downcast_ref::<ErrorKind>() {
+            Some(ErrorKind::IgnoredSetters { setters, .. }) => {
+                assert_eq!(setters, "initial_balance, memo")
+            }
+
+            other => panic!("unexpected error: {:?}", other),
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,88 @@
+use crate::{
+    proto::{self, ToProto, TransactionBody::TransactionBody_oneof_data},
+    transaction::Transaction,
+    Client, ContractId, ErrorKind, FileId,
+};
+use chrono::{DateTime, Utc};
+use failure::Error;
+use query_interface::{interfaces, vtable_for};
+use std::{any::Any, time::Duration};
+
+// The entity an admin transaction acts on
+#[derive(Debug, Clone, Copy)]
+pub(super) enum AdminEntity {
+    File(FileId),
+    Contract(ContractId),
+}
+
+// Delete a file or contract with the Hedera admin multisig. Unlike a regular delete,
+// the entity is kept until its expiration time and can be recovered until then.
+pub struct TransactionAdminDelete {
+    entity: AdminEntity,
+    expiration_time: Option<DateTime<Utc>>,
+}
+
+/// `TransactionAdminDelete` under the name of the `SystemDelete` body it sends.
+pub type TransactionSystemDelete = TransactionAdminDelete;
+
+interfaces!(
+    TransactionAdminDelete: dyn Any,
+    dyn ToProto<TransactionBody_oneof_data>
+);
+
+impl TransactionAdminDelete {
+    pub fn file(client: &Client, id: FileId) -> Transaction<Self> {
+        Self::new(client, AdminEntity::File(id))
+    }
+
+    pub fn contract(client: &Client, id: ContractId) -> Transaction<Self> {
+        Self::new(client, AdminEntity::Contract(id))
+    }
+
+    fn new(client: &Client, entity: AdminEntity) -> Transaction<Self> {
+        Transaction::new(
+            client,
+            Self {
+                entity,
+                expiration_time: None,
+            },
+        )
+    }
+}
+
+impl Transaction<TransactionAdminDelete> {
+    /// When the deleted entity is permanently removed.
+    #[inline]
+    pub fn expires_at(&mut self, expiration: DateTime<Utc>) -> &mut Self {
+        if let Some(inner) = self.inner("expires_at") {
+            inner.expiration_time = Some(expiration);
+        }
+
+        self
+    }
+
+    #[inline]
+    pub fn expires_in(&mut self, duration: Duration) -> &mut Self {
+        self.expires_at(self.clock.now() + chrono::Duration::from_std(duration).unwrap())
+    }
+}
+
+impl ToProto<TransactionBody_oneof_data> for TransactionAdminDelete {
+    fn to_proto(&self) -> Result<TransactionBody_oneof_data, Error> {
+        let mut data = proto::SystemDelete::SystemDeleteTransactionBody::new();
+
+        match self.entity {
+            AdminEntity::File(id) => data.set_fileID(id.to_proto()?),
+            AdminEntity::Contract(id) => data.set_contractID(id.to_proto()?),
+        }
+
+        let expiration_time = match self.expiration_time.as_ref() {
+            Some(expiration_time) => expiration_time,
+            None => Err(ErrorKind::MissingField("expiration_time"))?,
+        };
+
+        data.set_expirationTime(expiration_time.to_proto()?);
+
+        Ok(TransactionBody_oneof_data::systemDelete(data))
+    }
+}
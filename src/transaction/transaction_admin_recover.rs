@@ -0,0 +1,55 @@
+use super::transaction_admin_delete::AdminEntity;
+use crate::{
+    proto::{self, ToProto, TransactionBody::TransactionBody_oneof_data},
+    transaction::Transaction,
+    Client, ContractId, FileId,
+};
+use failure::Error;
+use query_interface::{interfaces, vtable_for};
+use std::any::Any;
+
+// Recover a file or contract deleted with `TransactionAdminDelete`, before its expiration time.
+pub struct TransactionAdminRecover {
+    entity: AdminEntity,
+}
+
+/// `TransactionAdminRecover` under the name of the `SystemUndelete` body it sends.
+pub type TransactionSystemUndelete = TransactionAdminRecover;
+
+interfaces!(
+    TransactionAdminRecover: dyn Any,
+    dyn ToProto<TransactionBody_oneof_data>
+);
+
+impl TransactionAdminRecover {
+    pub fn file(client: &Client, id: FileId) -> Transaction<Self> {
+        Transaction::new(
+            client,
+            Self {
+                entity: AdminEntity::File(id),
+            },
+        )
+    }
+
+    pub fn contract(client: &Client, id: ContractId) -> Transaction<Self> {
+        Transaction::new(
+            client,
+            Self {
+                entity: AdminEntity::Contract(id),
+            },
+        )
+    }
+}
+
+impl ToProto<TransactionBody_oneof_data> for TransactionAdminRecover {
+    fn to_proto(&self) -> Result<TransactionBody_oneof_data, Error> {
+        let mut data = proto::SystemUndelete::SystemUndeleteTransactionBody::new();
+
+        match self.entity {
+            AdminEntity::File(id) => data.set_fileID(id.to_proto()?),
+            AdminEntity::Contract(id) => data.set_contractID(id.to_proto()?),
+        }
+
+        Ok(TransactionBody_oneof_data::systemUndelete(data))
+    }
+}
@@ -37,21 +37,30 @@ impl Transaction<TransactionContractCall> {
     /// The maximum amount of gas to use for the call.
     #[inline]
     pub fn gas(&mut self, gas: i64) -> &mut Self {
-        self.inner().gas = gas;
+        if let Some(inner) = self.inner("gas") {
+            inner.gas = gas;
+        }
+
         self
     }
 
     /// Number of tinybars to send (the function must be payable if this is nonzero).
     #[inline]
     pub fn amount(&mut self, amount: i64) -> &mut Self {
-        self.inner().amount = amount;
+        if let Some(inner) = self.inner("amount") {
+            inner.amount = amount;
+        }
+
         self
     }
 
     /// Which function to call, and the parameters to pass to the function.
     #[inline]
     pub fn function_parameters(&mut self, params: Vec<u8>) -> &mut Self {
-        self.inner().function_parameters = params;
+        if let Some(inner) = self.inner("function_parameters") {
+            inner.function_parameters = params;
+        }
+
         self
     }
 }
@@ -1,7 +1,8 @@
 use crate::{
     crypto::PublicKey,
+    duration::check_auto_renew_period,
     proto::{self, ToProto, TransactionBody::TransactionBody_oneof_data},
-    AccountId, FileId,
+    AccountId, ErrorKind, FileId,
 };
 
 use crate::{transaction::Transaction, Client};
@@ -17,6 +18,8 @@ pub struct TransactionContractCreate {
     proxy_account: Option<AccountId>,
     auto_renew_period: Duration,
     constructor_parameters: Option<Vec<u8>>,
+    shard: i64,
+    realm: i64,
 }
 
 interfaces!(
@@ -36,51 +39,84 @@ impl TransactionContractCreate {
                 proxy_account: None,
                 auto_renew_period: Duration::from_secs(7_890_000),
                 constructor_parameters: None,
+                shard: client.shard,
+                realm: client.realm,
             },
         )
     }
 }
 
 impl Transaction<TransactionContractCreate> {
+    /// The file holding the bytecode of the contract; required.
     #[inline]
     pub fn file(&mut self, id: FileId) -> &mut Self {
-        self.inner().file_id = Some(id);
+        if let Some(inner) = self.inner("file") {
+            inner.file_id = Some(id);
+        }
+
         self
     }
 
+    /// The gas to run the constructor with.
     #[inline]
     pub fn gas(&mut self, gas: i64) -> &mut Self {
-        self.inner().gas = gas;
+        if let Some(inner) = self.inner("gas") {
+            inner.gas = gas;
+        }
+
         self
     }
 
+    /// The key that can update or delete the contract. Without one, the contract
+    /// cannot be changed.
     #[inline]
     pub fn admin_key(&mut self, key: PublicKey) -> &mut Self {
-        self.inner().admin_key = Some(key);
+        if let Some(inner) = self.inner("admin_key") {
+            inner.admin_key = Some(key);
+        }
+
         self
     }
 
+    /// The tinybars to move from the payer into the account of the contract.
     #[inline]
     pub fn initial_balance(&mut self, balance: i64) -> &mut Self {
-        self.inner().initial_balance = balance;
+        if let Some(inner) = self.inner("initial_balance") {
+            inner.initial_balance = balance;
+        }
+
         self
     }
 
+    /// The account the contract is proxy staked to.
     #[inline]
     pub fn proxy_account(&mut self, account: AccountId) -> &mut Self {
-        self.inner().proxy_account = Some(account);
+        if let Some(inner) = self.inner("proxy_account") {
+            inner.proxy_account = Some(account);
+        }
+
         self
     }
 
+    /// How often the contract is charged to extend its expiration. Defaults to about
+    /// three months; must be between `network::MIN_AUTO_RENEW_PERIOD` and
+    /// `network::MAX_AUTO_RENEW_PERIOD`, or the transaction fails to build.
     #[inline]
     pub fn auto_renew_period(&mut self, period: Duration) -> &mut Self {
-        self.inner().auto_renew_period = period;
+        if let Some(inner) = self.inner("auto_renew_period") {
+            inner.auto_renew_period = period;
+        }
+
         self
     }
 
+    /// The ABI-encoded parameters of the constructor, e.g. from `CallParams::assemble`.
     #[inline]
     pub fn constructor_parameters(&mut self, params: Vec<u8>) -> &mut Self {
-        self.inner().constructor_parameters = Some(params);
+        if let Some(inner) = self.inner("constructor_parameters") {
+            inner.constructor_parameters = Some(params);
+        }
+
         self
     }
 }
@@ -90,11 +126,11 @@ impl ToProto<TransactionBody_oneof_data> for TransactionContractCreate {
         let mut data = proto::ContractCreate::ContractCreateTransactionBody::new();
 
         let mut shard = proto::BasicTypes::ShardID::new();
-        shard.set_shardNum(0);
+        shard.set_shardNum(self.shard);
         data.set_shardID(shard);
 
         let mut realm = proto::BasicTypes::RealmID::new();
-        realm.set_realmNum(0);
+        realm.set_realmNum(self.realm);
         data.set_realmID(realm);
 
         data.set_initialBalance(self.initial_balance);
@@ -103,14 +139,16 @@ impl ToProto<TransactionBody_oneof_data> for TransactionContractCreate {
             data.set_proxyAccountID(account.to_proto()?);
         }
 
-        if let Some(id) = self.file_id {
-            data.set_fileID(id.to_proto()?);
+        match self.file_id {
+            Some(id) => data.set_fileID(id.to_proto()?),
+            None => Err(ErrorKind::MissingField("file"))?,
         }
 
         if let Some(key) = &self.admin_key {
             data.set_adminKey(key.to_proto()?);
         }
 
+        check_auto_renew_period(self.auto_renew_period)?;
         data.set_autoRenewPeriod(self.auto_renew_period.to_proto()?);
 
         data.set_gas(self.gas);
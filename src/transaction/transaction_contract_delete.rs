@@ -33,7 +33,10 @@ impl TransactionContractDelete {
 impl Transaction<TransactionContractDelete> {
     #[inline]
     pub fn obtainer_account(&mut self, acct: AccountId) -> &mut Self {
-        self.inner().obtainer_account = Some(acct);
+        if let Some(inner) = self.inner("obtainer_account") {
+            inner.obtainer_account = Some(acct);
+        }
+
         self
     }
 }
@@ -1,5 +1,6 @@
 use crate::{
     crypto::PublicKey,
+    duration::check_auto_renew_period,
     id::{AccountId, ContractId, FileId},
     proto::{self, ToProto, TransactionBody::TransactionBody_oneof_data},
     transaction::Transaction,
@@ -43,36 +44,53 @@ impl TransactionContractUpdate {
 impl Transaction<TransactionContractUpdate> {
     #[inline]
     pub fn expires_at(&mut self, expiration: DateTime<Utc>) -> &mut Self {
-        self.inner().expiration_time = Some(expiration);
+        if let Some(inner) = self.inner("expires_at") {
+            inner.expiration_time = Some(expiration);
+        }
+
         self
     }
 
     #[inline]
     pub fn expires_in(&mut self, duration: Duration) -> &mut Self {
-        self.expires_at(Utc::now() + chrono::Duration::from_std(duration).unwrap())
+        self.expires_at(self.clock.now() + chrono::Duration::from_std(duration).unwrap())
     }
 
     #[inline]
     pub fn admin_key(&mut self, key: PublicKey) -> &mut Self {
-        self.inner().admin_key = Some(key);
+        if let Some(inner) = self.inner("admin_key") {
+            inner.admin_key = Some(key);
+        }
+
         self
     }
 
     #[inline]
     pub fn proxy_account(&mut self, account: AccountId) -> &mut Self {
-        self.inner().proxy_account = Some(account);
+        if let Some(inner) = self.inner("proxy_account") {
+            inner.proxy_account = Some(account);
+        }
+
         self
     }
 
+    /// Must be between `network::MIN_AUTO_RENEW_PERIOD` and `network::MAX_AUTO_RENEW_PERIOD`,
+    /// or the transaction fails to build.
     #[inline]
     pub fn auto_renew_period(&mut self, duration: Duration) -> &mut Self {
-        self.inner().auto_renew_period = Some(duration);
+        if let Some(inner) = self.inner("auto_renew_period") {
+            inner.auto_renew_period = Some(duration);
+        }
+
         self
     }
 
     #[inline]
     pub fn file(&mut self, file: FileId) -> &mut Self {
-        self.inner().file = Some(file);
+        if let Some(inner) = self.inner("file") {
+            inner.file = Some(file);
+        }
+
         self
     }
 }
@@ -95,6 +113,7 @@ impl ToProto<TransactionBody_oneof_data> for TransactionContractUpdate {
         }
 
         if let Some(duration) = self.auto_renew_period.as_ref() {
+            check_auto_renew_period(*duration)?;
             data.set_autoRenewPeriod(duration.to_proto()?);
         }
 
@@ -12,9 +12,7 @@ use std::any::Any;
 
 #[derive(Debug)]
 pub struct TransactionCryptoAddClaim {
-    account: AccountId,
-    hash: Vec<u8>,
-    keys: Vec<PublicKey>,
+    claim: Claim,
 }
 
 interfaces!(
@@ -24,21 +22,21 @@ interfaces!(
 
 impl TransactionCryptoAddClaim {
     pub fn new(client: &Client, account: AccountId, hash: Vec<u8>) -> Transaction<Self> {
-        Transaction::new(
-            client,
-            Self {
-                account,
-                hash,
-                keys: Vec::new(),
-            },
-        )
+        Self::from_claim(client, Claim::builder(account, hash).build())
+    }
+
+    pub fn from_claim(client: &Client, claim: Claim) -> Transaction<Self> {
+        Transaction::new(client, Self { claim })
     }
 }
 
 impl Transaction<TransactionCryptoAddClaim> {
     #[inline]
     pub fn key(&mut self, key: PublicKey) -> &mut Self {
-        self.inner().keys.push(key);
+        if let Some(inner) = self.inner("key") {
+            inner.claim.keys.push(key);
+        }
+
         self
     }
 }
@@ -46,14 +44,7 @@ impl Transaction<TransactionCryptoAddClaim> {
 impl ToProto<TransactionBody_oneof_data> for TransactionCryptoAddClaim {
     fn to_proto(&self) -> Result<TransactionBody_oneof_data, Error> {
         let mut data = proto::CryptoAddClaim::CryptoAddClaimTransactionBody::new();
-
-        let claim = Claim {
-            account: self.account,
-            hash: self.hash.clone(),
-            keys: self.keys.clone(),
-        };
-
-        data.set_claim(claim.to_proto()?);
+        data.set_claim(self.claim.to_proto()?);
 
         Ok(TransactionBody_oneof_data::cryptoAddClaim(data))
     }
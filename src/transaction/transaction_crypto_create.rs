@@ -1,5 +1,6 @@
 use crate::{
     crypto::PublicKey,
+    duration::check_auto_renew_period,
     proto::{self, ToProto, TransactionBody::TransactionBody_oneof_data},
     transaction::Transaction,
     AccountId, Client, ErrorKind,
@@ -17,6 +18,8 @@ pub struct TransactionCryptoCreate {
     receiver_signature_required: bool,
     proxy_account: Option<AccountId>,
     auto_renew_period: Duration,
+    shard: i64,
+    realm: i64,
 }
 
 interfaces!(
@@ -36,6 +39,8 @@ impl TransactionCryptoCreate {
                 receiver_signature_required: false,
                 proxy_account: None,
                 auto_renew_period: Duration::from_secs(7_890_000),
+                shard: client.shard,
+                realm: client.realm,
             },
         )
     }
@@ -44,19 +49,28 @@ impl TransactionCryptoCreate {
 impl Transaction<TransactionCryptoCreate> {
     #[inline]
     pub fn key(&mut self, key: PublicKey) -> &mut Self {
-        self.inner().key = Some(key);
+        if let Some(inner) = self.inner("key") {
+            inner.key = Some(key);
+        }
+
         self
     }
 
     #[inline]
     pub fn initial_balance(&mut self, balance: u64) -> &mut Self {
-        self.inner().initial_balance = balance;
+        if let Some(inner) = self.inner("initial_balance") {
+            inner.initial_balance = balance;
+        }
+
         self
     }
 
     #[inline]
     pub fn proxy_account(&mut self, account: AccountId) -> &mut Self {
-        self.inner().proxy_account = Some(account);
+        if let Some(inner) = self.inner("proxy_account") {
+            inner.proxy_account = Some(account);
+        }
+
         self
     }
 
@@ -64,37 +78,48 @@ impl Transaction<TransactionCryptoCreate> {
     /// If it doesn't have enough, it extends as long as possible.
     /// If it is empty when it expires, then it is deleted.
     ///
-    /// Defaults to `2_592_000` seconds.
+    /// Defaults to `7_890_000` seconds. Must be between `network::MIN_AUTO_RENEW_PERIOD` and
+    /// `network::MAX_AUTO_RENEW_PERIOD`, or the transaction fails to build.
     #[inline]
     pub fn auto_renew_period(&mut self, period: Duration) -> &mut Self {
-        self.inner().auto_renew_period = period;
+        if let Some(inner) = self.inner("auto_renew_period") {
+            inner.auto_renew_period = period;
+        }
+
         self
     }
 
     /// Create an account record for any transaction withdrawing more than this many tinybars.
+    /// A negative threshold fails to build.
     #[inline]
     pub fn send_record_threshold(&mut self, threshold: i64) -> &mut Self {
-        debug_assert!(threshold > 0);
+        if let Some(inner) = self.inner("send_record_threshold") {
+            inner.send_record_threshold = threshold;
+        }
 
-        self.inner().send_record_threshold = threshold;
         self
     }
 
     /// Create an account record for any transaction depositing more than this many tinybars.
+    /// A negative threshold fails to build.
     #[inline]
     pub fn receive_record_threshold(&mut self, threshold: i64) -> &mut Self {
-        debug_assert!(threshold > 0);
+        if let Some(inner) = self.inner("receive_record_threshold") {
+            inner.receive_record_threshold = threshold;
+        }
 
-        self.inner().receive_record_threshold = threshold;
         self
     }
 
     /// If true, this account's key must sign any transaction depositing into this
-    /// account (in addition to all withdrawals). This field is immutable; it cannot be
-    /// changed by a CryptoUpdate transaction.
+    /// account (in addition to all withdrawals). It can be changed later with
+    /// `receiver_signature_required` on a crypto update.
     #[inline]
     pub fn receiver_signature_required(&mut self, required: bool) -> &mut Self {
-        self.inner().receiver_signature_required = required;
+        if let Some(inner) = self.inner("receiver_signature_required") {
+            inner.receiver_signature_required = required;
+        }
+
         self
     }
 }
@@ -104,16 +129,16 @@ impl ToProto<TransactionBody_oneof_data> for TransactionCryptoCreate {
         let mut data = proto::CryptoCreate::CryptoCreateTransactionBody::new();
 
         let mut shard = proto::BasicTypes::ShardID::new();
-        shard.set_shardNum(0);
+        shard.set_shardNum(self.shard);
         data.set_shardID(shard);
 
         let mut realm = proto::BasicTypes::RealmID::new();
-        realm.set_realmNum(0);
+        realm.set_realmNum(self.realm);
         data.set_realmID(realm);
 
         data.set_initialBalance(self.initial_balance);
-        data.set_sendRecordThreshold(self.send_record_threshold.try_into()?);
-        data.set_receiveRecordThreshold(self.receive_record_threshold.try_into()?);
+        data.set_sendRecordThreshold(record_threshold(self.send_record_threshold)?);
+        data.set_receiveRecordThreshold(record_threshold(self.receive_record_threshold)?);
         data.set_receiverSigRequired(self.receiver_signature_required);
 
         if let Some(account) = self.proxy_account {
@@ -126,8 +151,15 @@ impl ToProto<TransactionBody_oneof_data> for TransactionCryptoCreate {
         };
 
         data.set_key(key.to_proto()?);
+        check_auto_renew_period(self.auto_renew_period)?;
         data.set_autoRenewPeriod(self.auto_renew_period.to_proto()?);
 
         Ok(TransactionBody_oneof_data::cryptoCreateAccount(data))
     }
 }
+
+fn record_threshold(threshold: i64) -> Result<u64, Error> {
+    Ok(threshold
+        .try_into()
+        .map_err(|_| ErrorKind::NegativeRecordThreshold(threshold))?)
+}
@@ -33,7 +33,9 @@ impl TransactionCryptoDelete {
 
 impl Transaction<TransactionCryptoDelete> {
     pub fn transfer_to(&mut self, id: AccountId) {
-        self.inner().transfer_to = Some(id);
+        if let Some(inner) = self.inner("transfer_to") {
+            inner.transfer_to = Some(id);
+        }
     }
 }
 
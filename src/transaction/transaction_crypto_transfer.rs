@@ -1,12 +1,12 @@
 use crate::{
     proto::{self, ToProto, TransactionBody::TransactionBody_oneof_data},
     transaction::Transaction,
-    AccountId, Client,
+    AccountId, Client, ErrorKind,
 };
 use failure::Error;
 use protobuf::RepeatedField;
 use query_interface::{interfaces, vtable_for};
-use std::any::Any;
+use std::{any::Any, collections::HashSet};
 
 impl From<proto::CryptoTransfer::TransferList> for Vec<(AccountId, i64)> {
     fn from(mut transfers: proto::CryptoTransfer::TransferList) -> Self {
@@ -39,15 +39,30 @@ impl TransactionCryptoTransfer {
 }
 
 impl Transaction<TransactionCryptoTransfer> {
+    /// Move `amount` tinybars into the account `id`, or out of it if `amount` is negative.
+    ///
+    /// The amounts must sum to zero and each account may appear only once, or the
+    /// transaction fails to build with `ErrorKind::InvalidTransferList`.
     #[inline]
     pub fn transfer(&mut self, id: AccountId, amount: i64) -> &mut Self {
-        self.inner().transfers.push((id, amount));
+        if let Some(inner) = self.inner("transfer") {
+            inner.transfers.push((id, amount));
+        }
+
         self
     }
+
+    /// The transfers added so far, e.g. to show them for confirmation before signing.
+    pub fn get_transfers(&self) -> &[(AccountId, i64)] {
+        self.inner_ref()
+            .map_or(&[][..], |inner| inner.transfers.as_slice())
+    }
 }
 
 impl ToProto<TransactionBody_oneof_data> for TransactionCryptoTransfer {
     fn to_proto(&self) -> Result<TransactionBody_oneof_data, Error> {
+        check_transfers(&self.transfers)?;
+
         let amounts: Result<Vec<proto::CryptoTransfer::AccountAmount>, Error> = self
             .transfers
             .iter()
@@ -68,3 +83,48 @@ impl ToProto<TransactionBody_oneof_data> for TransactionCryptoTransfer {
         Ok(TransactionBody_oneof_data::cryptoTransfer(data))
     }
 }
+
+// The network refuses a transfer list that does not balance or that names an account twice
+fn check_transfers(transfers: &[(AccountId, i64)]) -> Result<(), ErrorKind> {
+    let mut accounts = HashSet::new();
+
+    for (id, _) in transfers {
+        if !accounts.insert(id) {
+            return Err(ErrorKind::InvalidTransferList(format!(
+                "{} appears more than once",
+                id
+            )));
+        }
+    }
+
+    let sum: i128 = transfers.iter().map(|(_, amount)| i128::from(*amount)).sum();
+
+    if sum != 0 {
+        return Err(ErrorKind::InvalidTransferList(format!(
+            "the amounts sum to {} instead of 0",
+            sum
+        )));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::check_transfers;
+    use crate::AccountId;
+
+    #[test]
+    fn test_check_transfers() {
+        let (a, b) = (AccountId::new(0, 0, 2), AccountId::new(0, 0, 1001));
+        let (c, d) = (AccountId::new(0, 0, 1002), AccountId::new(0, 0, 1003));
+        let (max, min) = (i64::max_value(), i64::min_value());
+
+        assert!(check_transfers(&[(a, -100), (b, 100)]).is_ok());
+        assert!(check_transfers(&[(a, -100), (b, 99)]).is_err());
+        assert!(check_transfers(&[(a, -100), (a, 100)]).is_err());
+
+        // the sum must not overflow on the way to zero
+        assert!(check_transfers(&[(a, max), (b, max), (c, min), (d, min + 2)]).is_ok());
+    }
+}
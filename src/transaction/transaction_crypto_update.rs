@@ -5,11 +5,13 @@ use query_interface::{interfaces, vtable_for};
 
 use crate::{
     crypto::PublicKey,
+    duration::check_auto_renew_period,
     proto::{self, ToProto, TransactionBody::TransactionBody_oneof_data},
     transaction::Transaction,
     AccountId, Client,
 };
 use chrono::{DateTime, Utc};
+use protobuf::well_known_types::{BoolValue, UInt64Value};
 use std::time::Duration;
 
 pub struct TransactionCryptoUpdate {
@@ -20,6 +22,7 @@ pub struct TransactionCryptoUpdate {
     receive_record_threshold: Option<u64>,
     auto_renew_period: Option<Duration>,
     expiration_time: Option<DateTime<Utc>>,
+    receiver_signature_required: Option<bool>,
 }
 
 interfaces!(
@@ -39,51 +42,84 @@ impl TransactionCryptoUpdate {
                 receive_record_threshold: None,
                 auto_renew_period: None,
                 expiration_time: None,
+                receiver_signature_required: None,
             },
         )
     }
 }
 
 impl Transaction<TransactionCryptoUpdate> {
+    /// Replace the key of the account; the transaction must be signed by both the old and
+    /// the new key.
     #[inline]
     pub fn key(&mut self, key: PublicKey) -> &mut Self {
-        self.inner().key = Some(key);
+        if let Some(inner) = self.inner("key") {
+            inner.key = Some(key);
+        }
+
         self
     }
 
     #[inline]
     pub fn proxy_account(&mut self, proxy_account: AccountId) -> &mut Self {
-        self.inner().proxy_account = Some(proxy_account);
+        if let Some(inner) = self.inner("proxy_account") {
+            inner.proxy_account = Some(proxy_account);
+        }
+
         self
     }
 
     #[inline]
     pub fn send_record_threshold(&mut self, send_record_threshold: u64) -> &mut Self {
-        self.inner().send_record_threshold = Some(send_record_threshold);
+        if let Some(inner) = self.inner("send_record_threshold") {
+            inner.send_record_threshold = Some(send_record_threshold);
+        }
+
         self
     }
 
     #[inline]
     pub fn receive_record_threshold(&mut self, receive_record_threshold: u64) -> &mut Self {
-        self.inner().receive_record_threshold = Some(receive_record_threshold);
+        if let Some(inner) = self.inner("receive_record_threshold") {
+            inner.receive_record_threshold = Some(receive_record_threshold);
+        }
+
         self
     }
 
+    /// Must be between `network::MIN_AUTO_RENEW_PERIOD` and `network::MAX_AUTO_RENEW_PERIOD`,
+    /// or the transaction fails to build.
     #[inline]
     pub fn auto_renew_period(&mut self, auto_renew_period: Duration) -> &mut Self {
-        self.inner().auto_renew_period = Some(auto_renew_period);
+        if let Some(inner) = self.inner("auto_renew_period") {
+            inner.auto_renew_period = Some(auto_renew_period);
+        }
+
+        self
+    }
+
+    /// Whether the key of the account must also sign transfers into it.
+    #[inline]
+    pub fn receiver_signature_required(&mut self, required: bool) -> &mut Self {
+        if let Some(inner) = self.inner("receiver_signature_required") {
+            inner.receiver_signature_required = Some(required);
+        }
+
         self
     }
 
     #[inline]
     pub fn expires_at(&mut self, expiration: DateTime<Utc>) -> &mut Self {
-        self.inner().expiration_time = Some(expiration);
+        if let Some(inner) = self.inner("expires_at") {
+            inner.expiration_time = Some(expiration);
+        }
+
         self
     }
 
     #[inline]
     pub fn expires_in(&mut self, duration: Duration) -> &mut Self {
-        self.expires_at(Utc::now() + chrono::Duration::from_std(duration).unwrap())
+        self.expires_at(self.clock.now() + chrono::Duration::from_std(duration).unwrap())
     }
 }
 
@@ -100,15 +136,28 @@ impl ToProto<TransactionBody_oneof_data> for TransactionCryptoUpdate {
             data.set_proxyAccountID(proxy_account.to_proto()?);
         }
 
-        if let Some(send_record_threshold) = self.send_record_threshold.as_ref() {
-            data.set_sendRecordThreshold(*send_record_threshold);
+        // the wrapped fields tell a value of zero (or false) apart from one that is not set
+
+        if let Some(send_record_threshold) = self.send_record_threshold {
+            let mut value = UInt64Value::new();
+            value.set_value(send_record_threshold);
+            data.set_sendRecordThresholdWrapper(value);
+        }
+
+        if let Some(receive_record_threshold) = self.receive_record_threshold {
+            let mut value = UInt64Value::new();
+            value.set_value(receive_record_threshold);
+            data.set_receiveRecordThresholdWrapper(value);
         }
 
-        if let Some(receive_record_threshold) = self.receive_record_threshold.as_ref() {
-            data.set_receiveRecordThreshold(*receive_record_threshold);
+        if let Some(required) = self.receiver_signature_required {
+            let mut value = BoolValue::new();
+            value.set_value(required);
+            data.set_receiverSigRequiredWrapper(value);
         }
 
         if let Some(auto_renew_period) = self.auto_renew_period.as_ref() {
+            check_auto_renew_period(*auto_renew_period)?;
             data.set_autoRenewPeriod(auto_renew_period.to_proto()?);
         }
 
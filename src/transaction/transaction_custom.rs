@@ -0,0 +1,35 @@
+use crate::{
+    proto::{ToProto, TransactionBody::TransactionBody_oneof_data},
+    transaction::Transaction,
+    Client,
+};
+use failure::Error;
+use query_interface::{interfaces, vtable_for};
+use std::any::Any;
+
+/// A transaction with a body built by the caller from the protobufs, for a kind of
+/// transaction that has no builder in this crate yet.
+///
+/// The transaction is signed, retried and sent like any other, but only kinds that the
+/// services of this crate can send are accepted by `execute`; others fail with
+/// `ErrorKind::UnsupportedTransactionKind`.
+pub struct TransactionCustom {
+    data: TransactionBody_oneof_data,
+}
+
+interfaces!(
+    TransactionCustom: dyn Any,
+    dyn ToProto<TransactionBody_oneof_data>
+);
+
+impl TransactionCustom {
+    pub fn new(client: &Client, data: TransactionBody_oneof_data) -> Transaction<Self> {
+        Transaction::new(client, Self { data })
+    }
+}
+
+impl ToProto<TransactionBody_oneof_data> for TransactionCustom {
+    fn to_proto(&self) -> Result<TransactionBody_oneof_data, Error> {
+        Ok(self.data.clone())
+    }
+}
@@ -13,6 +13,8 @@ pub struct TransactionFileCreate {
     expiration_time: Option<DateTime<Utc>>,
     key: Option<PublicKey>,
     bytes: Vec<u8>,
+    shard: i64,
+    realm: i64,
 }
 
 interfaces!(
@@ -28,6 +30,8 @@ impl TransactionFileCreate {
                 expiration_time: None,
                 key: None,
                 bytes: Vec::new(),
+                shard: client.shard,
+                realm: client.realm,
             },
         )
     }
@@ -36,24 +40,33 @@ impl TransactionFileCreate {
 impl Transaction<TransactionFileCreate> {
     #[inline]
     pub fn expires_at(&mut self, expiration: DateTime<Utc>) -> &mut Self {
-        self.inner().expiration_time = Some(expiration);
+        if let Some(inner) = self.inner("expires_at") {
+            inner.expiration_time = Some(expiration);
+        }
+
         self
     }
 
     #[inline]
     pub fn expires_in(&mut self, duration: Duration) -> &mut Self {
-        self.expires_at(Utc::now() + chrono::Duration::from_std(duration).unwrap())
+        self.expires_at(self.clock.now() + chrono::Duration::from_std(duration).unwrap())
     }
 
     #[inline]
     pub fn key(&mut self, key: PublicKey) -> &mut Self {
-        self.inner().key = Some(key);
+        if let Some(inner) = self.inner("key") {
+            inner.key = Some(key);
+        }
+
         self
     }
 
     #[inline]
     pub fn contents(&mut self, bytes: Vec<u8>) -> &mut Self {
-        self.inner().bytes = bytes;
+        if let Some(inner) = self.inner("contents") {
+            inner.bytes = bytes;
+        }
+
         self
     }
 }
@@ -63,11 +76,11 @@ impl ToProto<TransactionBody_oneof_data> for TransactionFileCreate {
         let mut data = proto::FileCreate::FileCreateTransactionBody::new();
 
         let mut shard = proto::BasicTypes::ShardID::new();
-        shard.set_shardNum(0);
+        shard.set_shardNum(self.shard);
         data.set_shardID(shard);
 
         let mut realm = proto::BasicTypes::RealmID::new();
-        realm.set_realmNum(0);
+        realm.set_realmNum(self.realm);
         data.set_realmID(realm);
 
         if let Some(expiration_time) = &self.expiration_time.as_ref() {
@@ -39,24 +39,33 @@ impl TransactionFileUpdate {
 impl Transaction<TransactionFileUpdate> {
     #[inline]
     pub fn expires_at(&mut self, expiration: DateTime<Utc>) -> &mut Self {
-        self.inner().expiration_time = Some(expiration);
+        if let Some(inner) = self.inner("expires_at") {
+            inner.expiration_time = Some(expiration);
+        }
+
         self
     }
 
     #[inline]
     pub fn expires_in(&mut self, duration: Duration) -> &mut Self {
-        self.expires_at(Utc::now() + chrono::Duration::from_std(duration).unwrap())
+        self.expires_at(self.clock.now() + chrono::Duration::from_std(duration).unwrap())
     }
 
     #[inline]
     pub fn key(&mut self, key: PublicKey) -> &mut Self {
-        self.inner().keys.push(key);
+        if let Some(inner) = self.inner("key") {
+            inner.keys.push(key);
+        }
+
         self
     }
 
     #[inline]
     pub fn contents(&mut self, bytes: Vec<u8>) -> &mut Self {
-        self.inner().bytes = bytes;
+        if let Some(inner) = self.inner("contents") {
+            inner.bytes = bytes;
+        }
+
         self
     }
 }
@@ -71,15 +80,18 @@ impl ToProto<TransactionBody_oneof_data> for TransactionFileUpdate {
             data.set_expirationTime(expiration_time.to_proto()?);
         }
 
-        let mut key_list = proto::BasicTypes::KeyList::new();
-        key_list.set_keys(RepeatedField::from_vec(
-            self.keys
-                .iter()
-                .map(ToProto::to_proto)
-                .collect::<Result<Vec<_>, _>>()?,
-        ));
+        // an empty key list would make the file immutable; leave the keys alone instead
+        if !self.keys.is_empty() {
+            let mut key_list = proto::BasicTypes::KeyList::new();
+            key_list.set_keys(RepeatedField::from_vec(
+                self.keys
+                    .iter()
+                    .map(ToProto::to_proto)
+                    .collect::<Result<Vec<_>, _>>()?,
+            ));
 
-        data.set_keys(key_list);
+            data.set_keys(key_list);
+        }
 
         data.set_contents(self.bytes.clone());
 
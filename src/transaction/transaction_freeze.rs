@@ -0,0 +1,79 @@
+use crate::{
+    proto::{self, ToProto, TransactionBody::TransactionBody_oneof_data},
+    transaction::Transaction,
+    Client, ErrorKind,
+};
+use failure::Error;
+use query_interface::{interfaces, vtable_for};
+use std::any::Any;
+
+// Freeze the network for maintenance between two times of day (UTC), with the Hedera admin
+// multisig. The nodes stop accepting transactions from the start until the end.
+pub struct TransactionFreeze {
+    start: Option<(u8, u8)>,
+    end: Option<(u8, u8)>,
+}
+
+interfaces!(
+    TransactionFreeze: dyn Any,
+    dyn ToProto<TransactionBody_oneof_data>
+);
+
+impl TransactionFreeze {
+    pub fn new(client: &Client) -> Transaction<Self> {
+        Transaction::new(
+            client,
+            Self {
+                start: None,
+                end: None,
+            },
+        )
+    }
+}
+
+impl Transaction<TransactionFreeze> {
+    /// The time of day (UTC) the freeze starts.
+    #[inline]
+    pub fn start(&mut self, hour: u8, minute: u8) -> &mut Self {
+        if let Some(inner) = self.inner("start") {
+            inner.start = Some((hour, minute));
+        }
+
+        self
+    }
+
+    /// The time of day (UTC) the freeze ends.
+    #[inline]
+    pub fn end(&mut self, hour: u8, minute: u8) -> &mut Self {
+        if let Some(inner) = self.inner("end") {
+            inner.end = Some((hour, minute));
+        }
+
+        self
+    }
+}
+
+fn check_time((hour, minute): (u8, u8)) -> Result<(i32, i32), Error> {
+    if hour > 23 || minute > 59 {
+        Err(ErrorKind::InvalidFreezeTime { hour, minute })?;
+    }
+
+    Ok((i32::from(hour), i32::from(minute)))
+}
+
+impl ToProto<TransactionBody_oneof_data> for TransactionFreeze {
+    fn to_proto(&self) -> Result<TransactionBody_oneof_data, Error> {
+        let (start_hour, start_minute) =
+            check_time(self.start.ok_or(ErrorKind::MissingField("start"))?)?;
+
+        let (end_hour, end_minute) = check_time(self.end.ok_or(ErrorKind::MissingField("end"))?)?;
+
+        let mut data = proto::Freeze::FreezeTransactionBody::new();
+        data.set_startHour(start_hour);
+        data.set_startMin(start_minute);
+        data.set_endHour(end_hour);
+        data.set_endMin(end_minute);
+
+        Ok(TransactionBody_oneof_data::freeze(data))
+    }
+}
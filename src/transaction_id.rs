@@ -1,7 +1,7 @@
 use chrono::{DateTime, Duration, Utc};
 use failure::Error;
 use itertools::Itertools;
-use std::{fmt, str::FromStr};
+use std::{cmp::Ordering, fmt, str::FromStr};
 
 use crate::{
     error::ErrorKind,
@@ -9,7 +9,8 @@ use crate::{
     AccountId,
 };
 
-#[derive(Debug, Clone, PartialEq)]
+/// Transaction IDs are ordered by their valid start, then by account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct TransactionId {
     pub account_id: AccountId,
     pub transaction_valid_start: DateTime<Utc>,
@@ -17,15 +18,34 @@ pub struct TransactionId {
 
 impl TransactionId {
     pub fn new(account_id: AccountId) -> Self {
+        Self::generated_at(account_id, Utc::now())
+    }
+
+    /// The ID of a transaction from `account_id` generated at `now`, as read from the clock
+    /// of a client.
+    pub fn generated_at(account_id: AccountId, now: DateTime<Utc>) -> Self {
         Self {
             account_id,
             // Allows the transaction to be accepted as long as the
             // server is not more than 10 seconds behind us
-            transaction_valid_start: Utc::now() - Duration::seconds(10),
+            transaction_valid_start: now - Duration::seconds(10),
         }
     }
 }
 
+impl Ord for TransactionId {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.transaction_valid_start, self.account_id)
+            .cmp(&(other.transaction_valid_start, other.account_id))
+    }
+}
+
+impl PartialOrd for TransactionId {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl fmt::Display for TransactionId {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
         write!(
@@ -137,4 +157,25 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_ordering() {
+        let id = |num, seconds| TransactionId {
+            account_id: AccountId::new(0, 0, num),
+            transaction_valid_start: Timestamp(seconds, 0).into(),
+        };
+
+        let mut ids = vec![id(1001, 20), id(1002, 10), id(1001, 10)];
+        ids.sort();
+
+        assert_eq!(ids, vec![id(1001, 10), id(1002, 10), id(1001, 20)]);
+    }
+
+    #[test]
+    fn test_generated_at() {
+        let now = Timestamp(1539387985, 0).into();
+        let id = TransactionId::generated_at(AccountId::new(0, 0, 2), now);
+
+        assert_eq!(id.transaction_valid_start, Timestamp(1539387975, 0).into());
+    }
 }
@@ -1,4 +1,6 @@
-use crate::{proto, AccountId, ContractId, FileId, Status};
+use crate::{proto, AccountId, ContractId, Entity, ErrorKind, ExchangeRate, FileId, Status};
+use failure::Error;
+use std::fmt;
 
 #[repr(C)]
 #[derive(Debug, Clone)]
@@ -7,12 +9,82 @@ pub struct TransactionReceipt {
     pub account_id: Option<Box<AccountId>>,
     pub contract_id: Option<Box<ContractId>>,
     pub file_id: Option<Box<FileId>>,
+
+    /// The exchange rate in effect when the transaction reached consensus, to convert its
+    /// fee into US cents.
+    pub current_exchange_rate: Option<ExchangeRate>,
+
+    /// The exchange rate that takes over once the current one expires.
+    pub next_exchange_rate: Option<ExchangeRate>,
 }
 
-impl std::fmt::Display for TransactionReceipt {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "TX Receipt\n\tStatus: {:#?}\n\tAccount: {:#?}\n\tContract: {:#?}\n\tFile: {:#?}",
-               self.status, self.account_id, self.contract_id, self.file_id)
+impl TransactionReceipt {
+    /// The account created by the transaction, failing if there is none
+    /// (e.g. because the transaction failed).
+    pub fn account_id(&self) -> Result<AccountId, Error> {
+        Ok(**self.account_id.as_ref().ok_or_else(|| self.missing("account"))?)
+    }
+
+    /// The file created by the transaction, failing if there is none.
+    pub fn file_id(&self) -> Result<FileId, Error> {
+        Ok(**self.file_id.as_ref().ok_or_else(|| self.missing("file"))?)
+    }
+
+    /// The contract created by the transaction, failing if there is none.
+    pub fn contract_id(&self) -> Result<ContractId, Error> {
+        Ok(**self.contract_id.as_ref().ok_or_else(|| self.missing("contract"))?)
+    }
+
+    /// Whichever account, file or contract the transaction created.
+    pub fn created_entity(&self) -> Option<Entity> {
+        if let Some(id) = &self.account_id {
+            Some(Entity::Account(**id))
+        } else if let Some(id) = &self.file_id {
+            Some(Entity::File(**id))
+        } else if let Some(id) = &self.contract_id {
+            Some(Entity::Contract(**id))
+        } else {
+            None
+        }
+    }
+
+    fn missing(&self, kind: &'static str) -> ErrorKind {
+        ErrorKind::ReceiptMissingId {
+            kind,
+            status: self.status,
+        }
+    }
+
+    // Write the status and created entity as lines of `label: value`, aligned with the
+    // lines of a record
+    pub(crate) fn fmt_lines(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{:<12} {:?}", "status:", self.status)?;
+
+        if let Some(id) = &self.account_id {
+            writeln!(f, "{:<12} {}", "account:", id)?;
+        }
+
+        if let Some(id) = &self.contract_id {
+            writeln!(f, "{:<12} {}", "contract:", id)?;
+        }
+
+        if let Some(id) = &self.file_id {
+            writeln!(f, "{:<12} {}", "file:", id)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Formats the receipt as aligned lines, e.g.
+///
+/// ```text
+/// status:      Success
+/// account:     0:0:1001
+/// ```
+impl fmt::Display for TransactionReceipt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_lines(f)
     }
 }
 
@@ -36,11 +108,28 @@ impl From<proto::TransactionReceipt::TransactionReceipt> for TransactionReceipt
             None
         };
 
+        // an empty set if the receipt has none
+        let mut rates = receipt.take_exchangeRate();
+
+        let current_exchange_rate = if rates.has_currentRate() {
+            Some(rates.take_currentRate().into())
+        } else {
+            None
+        };
+
+        let next_exchange_rate = if rates.has_nextRate() {
+            Some(rates.take_nextRate().into())
+        } else {
+            None
+        };
+
         Self {
             status: receipt.get_status().into(),
             account_id,
             contract_id,
             file_id,
+            current_exchange_rate,
+            next_exchange_rate,
         }
     }
 }
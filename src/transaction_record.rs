@@ -1,6 +1,11 @@
-use crate::{id::AccountId, proto, function_result::ContractFunctionResult, TransactionReceipt};
+use crate::{
+    function_result::ContractFunctionResult, id::AccountId, proto, summary::hbar, ErrorKind,
+    Memo, Status, TransactionId, TransactionReceipt,
+};
 use chrono::{DateTime, Utc};
-use failure::{err_msg, Error};
+use failure::Error;
+use sha2::{Digest, Sha384};
+use std::{collections::HashMap, fmt};
 use try_from::{TryFrom, TryInto};
 
 #[derive(Debug, Clone)]
@@ -8,23 +13,231 @@ pub enum TransactionRecordBody {
     ContractCall(ContractFunctionResult),
     ContractCreate(ContractFunctionResult),
     Transfer(Vec<(AccountId, i64)>),
+
+    /// The record has no body this version of the SDK knows how to read, e.g. because it is
+    /// for a kind of transaction that was added to the network later.
+    Incomplete,
 }
 
 #[derive(Debug, Clone)]
 pub struct TransactionRecord {
+    pub transaction_id: TransactionId,
     pub receipt: TransactionReceipt,
     pub transaction_hash: Vec<u8>,
     pub consensus_timestamp: Option<DateTime<Utc>>,
-    pub memo: String,
+    pub memo: Memo,
     pub transaction_fee: u64,
     pub body: TransactionRecordBody,
 }
 
+/// An account whose net transfer in a record differs from what was expected,
+/// from `TransactionRecord::verify_transfer`.
+///
+/// The amounts are net sums of `i64` amounts, and so may not fit in an `i64` themselves.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransferMismatch {
+    pub account: AccountId,
+    pub expected: i128,
+    pub actual: i128,
+}
+
+impl fmt::Display for TransferMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: expected {}, got {}",
+            self.account,
+            hbar(self.expected),
+            hbar(self.actual)
+        )
+    }
+}
+
+impl TransactionRecord {
+    /// Check that the transfers of this record include the `expected` amount for each
+    /// account, net of the transaction fee paid by the payer.
+    ///
+    /// Accounts that are not expected (such as the node and the accounts that collect
+    /// the fee) are not checked. Every account whose amount differs is reported.
+    pub fn verify_transfer(
+        &self,
+        expected: &[(AccountId, i64)],
+    ) -> Result<(), Vec<TransferMismatch>> {
+        let mut actual = HashMap::new();
+
+        if let TransactionRecordBody::Transfer(transfers) = &self.body {
+            for (account, amount) in transfers {
+                *actual.entry(*account).or_insert(0) += i128::from(*amount);
+            }
+        }
+
+        // the payer is charged the fee on top of whatever it transferred
+        *actual.entry(self.transaction_id.account_id).or_insert(0) +=
+            i128::from(self.transaction_fee);
+
+        let mut net = HashMap::new();
+
+        for (account, amount) in expected {
+            *net.entry(*account).or_insert(0) += i128::from(*amount);
+        }
+
+        let mut mismatches: Vec<_> = net
+            .into_iter()
+            .filter_map(|(account, expected)| {
+                let actual = actual.get(&account).copied().unwrap_or(0);
+
+                if actual == expected {
+                    None
+                } else {
+                    Some(TransferMismatch {
+                        account,
+                        expected,
+                        actual,
+                    })
+                }
+            })
+            .collect();
+
+        if mismatches.is_empty() {
+            return Ok(());
+        }
+
+        mismatches.sort_by_key(|mismatch| mismatch.account);
+
+        Err(mismatches)
+    }
+
+    /// Check an archived record against the signed transaction it is for, as serialized by
+    /// `Transaction::to_bytes`, and check that the record agrees with itself.
+    ///
+    /// This is a lighter check for when no state proof is available: it catches records that
+    /// were altered after they were fetched or paired with the wrong transaction, but does not
+    /// prove that the network reached consensus on the record.
+    pub fn verify_integrity(&self, transaction: impl AsRef<[u8]>) -> Result<(), Error> {
+        use ErrorKind::InconsistentRecord;
+
+        let transaction = transaction.as_ref();
+
+        if Sha384::digest(transaction).as_slice() != &self.transaction_hash[..] {
+            Err(InconsistentRecord("transaction hash does not match the transaction"))?;
+        }
+
+        let mut tx: proto::Transaction::Transaction = protobuf::parse_from_bytes(transaction)?;
+        let body = tx.take_body();
+
+        if TransactionId::from(body.get_transactionID().clone()) != self.transaction_id {
+            Err(InconsistentRecord("transaction ID does not match the transaction"))?;
+        }
+
+        if self.transaction_fee > body.get_transactionFee() {
+            Err(InconsistentRecord("fee is more than the transaction allowed"))?;
+        }
+
+        let success = self.receipt.status == Status::Success;
+
+        match &self.body {
+            TransactionRecordBody::Transfer(transfers) => {
+                // an altered record may have amounts that overflow an `i64` when added up
+                let sum: i128 = transfers.iter().map(|(_, amount)| i128::from(*amount)).sum();
+
+                if sum != 0 {
+                    Err(InconsistentRecord("transfers do not sum to zero"))?;
+                }
+            }
+
+            TransactionRecordBody::ContractCall(result) => {
+                if success && !result.error_message.is_empty() {
+                    Err(InconsistentRecord("contract call failed but the status is SUCCESS"))?;
+                }
+            }
+
+            TransactionRecordBody::ContractCreate(result) => {
+                if success && !result.error_message.is_empty() {
+                    Err(InconsistentRecord("contract create failed but the status is SUCCESS"))?;
+                }
+
+                if success && self.receipt.contract_id.is_none() {
+                    Err(InconsistentRecord("contract was created but the receipt has no ID"))?;
+                }
+            }
+
+            TransactionRecordBody::Incomplete => {}
+        }
+
+        Ok(())
+    }
+}
+
+/// Formats the record as aligned lines, e.g.
+///
+/// ```text
+/// transaction: 0:0:1001@1568327412.500000000
+/// consensus:   2019-09-12 22:30:15.123456789 UTC
+/// status:      Success
+/// fee:         0.00083 ℏ
+/// transfers:   0:0:1001  -1.00083 ℏ
+///              0:0:3      0.00083 ℏ
+///              0:0:1002         1 ℏ
+/// ```
+impl fmt::Display for TransactionRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{:<12} {}", "transaction:", self.transaction_id)?;
+
+        if let Some(consensus) = &self.consensus_timestamp {
+            writeln!(f, "{:<12} {}", "consensus:", consensus)?;
+        }
+
+        self.receipt.fmt_lines(f)?;
+
+        writeln!(f, "{:<12} {}", "fee:", hbar(self.transaction_fee))?;
+
+        if !self.memo.is_empty() {
+            writeln!(f, "{:<12} {}", "memo:", self.memo)?;
+        }
+
+        match &self.body {
+            TransactionRecordBody::Transfer(transfers) => {
+                let accounts: Vec<_> = transfers.iter().map(|(id, _)| id.to_string()).collect();
+                let amounts: Vec<_> = transfers.iter().map(|(_, amount)| hbar(*amount)).collect();
+                let width = accounts.iter().map(String::len).max().unwrap_or(0);
+                let amount_width = amounts.iter().map(|s| s.chars().count()).max().unwrap_or(0);
+
+                for (i, (account, amount)) in accounts.iter().zip(&amounts).enumerate() {
+                    let label = if i == 0 { "transfers:" } else { "" };
+                    writeln!(
+                        f,
+                        "{:<12} {:<width$}  {:>amount_width$}",
+                        label,
+                        account,
+                        amount,
+                        width = width,
+                        amount_width = amount_width
+                    )?;
+                }
+            }
+
+            TransactionRecordBody::ContractCall(result)
+            | TransactionRecordBody::ContractCreate(result) => {
+                writeln!(f, "{:<12} {}", "gas used:", result.gas_used)?;
+
+                if !result.error_message.is_empty() {
+                    writeln!(f, "{:<12} {}", "error:", result.error_message)?;
+                }
+            }
+
+            TransactionRecordBody::Incomplete => {}
+        }
+
+        Ok(())
+    }
+}
+
 impl TryFrom<proto::TransactionRecord::TransactionRecord> for TransactionRecord {
     type Err = Error;
 
     fn try_from(mut record: proto::TransactionRecord::TransactionRecord) -> Result<Self, Error> {
         Ok(Self {
+            transaction_id: record.take_transactionID().into(),
             receipt: record.take_receipt().into(),
             transaction_hash: record.take_transactionHash(),
             consensus_timestamp: if record.has_consensusTimestamp() {
@@ -32,7 +245,7 @@ impl TryFrom<proto::TransactionRecord::TransactionRecord> for TransactionRecord
             } else {
                 None
             },
-            memo: record.take_memo(),
+            memo: record.take_memo().into(),
             transaction_fee: record.get_transactionFee(),
             body: {
                 if record.has_contractCallResult() {
@@ -42,7 +255,7 @@ impl TryFrom<proto::TransactionRecord::TransactionRecord> for TransactionRecord
                 } else if record.has_transferList() {
                     TransactionRecordBody::Transfer(record.take_transferList().into())
                 } else {
-                    Err(err_msg("transaction record contained no body"))?
+                    TransactionRecordBody::Incomplete
                 }
             },
         })
@@ -62,3 +275,161 @@ impl TryFrom<proto::ContractGetRecords::ContractGetRecordsResponse> for Vec<Tran
             .collect::<Result<Self, _>>()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{TransactionRecord, TransactionRecordBody, TransferMismatch};
+    use crate::{proto, proto::ToProto, AccountId, Status, TransactionId, TransactionReceipt};
+    use failure::Error;
+    use proptest::{collection::vec, prelude::*};
+    use protobuf::Message;
+    use sha2::{Digest, Sha384};
+    use try_from::TryFrom;
+
+    #[test]
+    fn test_verify_transfer_net_of_fee() {
+        let payer = AccountId::new(0, 0, 1001);
+        let payee = AccountId::new(0, 0, 1002);
+        let node = AccountId::new(0, 0, 3);
+
+        let record = TransactionRecord {
+            transaction_id: TransactionId::new(payer),
+            receipt: TransactionReceipt {
+                status: Status::Success,
+                account_id: None,
+                contract_id: None,
+                file_id: None,
+                current_exchange_rate: None,
+                next_exchange_rate: None,
+            },
+            transaction_hash: Vec::new(),
+            consensus_timestamp: None,
+            memo: Default::default(),
+            transaction_fee: 83_000,
+            body: TransactionRecordBody::Transfer(vec![
+                (payer, -100_083_000),
+                (node, 83_000),
+                (payee, 100_000_000),
+            ]),
+        };
+
+        assert_eq!(
+            record.verify_transfer(&[(payer, -100_000_000), (payee, 100_000_000)]),
+            Ok(())
+        );
+
+        assert_eq!(
+            record.verify_transfer(&[(payee, 200_000_000)]),
+            Err(vec![TransferMismatch {
+                account: payee,
+                expected: 200_000_000,
+                actual: 100_000_000,
+            }])
+        );
+
+        // amounts that overflow an `i64` when added up are reported as they are
+        assert_eq!(
+            record.verify_transfer(&[(payee, i64::max_value()), (payee, i64::max_value())]),
+            Err(vec![TransferMismatch {
+                account: payee,
+                expected: 2 * i128::from(i64::max_value()),
+                actual: 100_000_000,
+            }])
+        );
+    }
+
+    #[test]
+    fn test_verify_integrity_of_large_transfers() -> Result<(), Error> {
+        let payer = AccountId::new(0, 0, 1001);
+        let id = TransactionId::new(payer);
+
+        let mut tx = proto::Transaction::Transaction::new();
+        tx.mut_body().set_transactionID(id.to_proto()?);
+        let bytes = tx.write_to_bytes()?;
+
+        let record = |transfers| TransactionRecord {
+            transaction_id: id,
+            receipt: TransactionReceipt {
+                status: Status::Success,
+                account_id: None,
+                contract_id: None,
+                file_id: None,
+                current_exchange_rate: None,
+                next_exchange_rate: None,
+            },
+            transaction_hash: Sha384::digest(&bytes).to_vec(),
+            consensus_timestamp: None,
+            memo: Default::default(),
+            transaction_fee: 0,
+            body: TransactionRecordBody::Transfer(transfers),
+        };
+
+        let (max, min) = ((payer, i64::max_value()), (payer, -i64::max_value()));
+        let balanced = record(vec![max, max, min, min]);
+        let unbalanced = record(vec![max, max]);
+
+        assert!(balanced.verify_integrity(&bytes).is_ok());
+        assert!(unbalanced.verify_integrity(&bytes).is_err());
+
+        Ok(())
+    }
+
+    proptest! {
+        // the network may leave out any optional field, or send a body of a newer kind
+        #[test]
+        fn test_partial_record_converts(
+            has_id in any::<bool>(),
+            has_receipt in any::<bool>(),
+            consensus in proptest::option::of((any::<i64>(), any::<i32>())),
+            fee in any::<u64>(),
+            body in 0..4
+        ) {
+            let mut record = proto::TransactionRecord::TransactionRecord::new();
+            record.set_transactionFee(fee);
+
+            if has_id {
+                record.mut_transactionID().mut_accountID().set_accountNum(1001);
+            }
+
+            if has_receipt {
+                record.mut_receipt().mut_accountID().set_accountNum(1002);
+            }
+
+            if let Some((seconds, nanos)) = consensus {
+                record.mut_consensusTimestamp().set_seconds(seconds);
+                record.mut_consensusTimestamp().set_nanos(nanos);
+            }
+
+            match body {
+                0 => record.mut_transferList().mut_accountAmounts().push(Default::default()),
+                1 => record.mut_contractCallResult().set_gasUsed(fee),
+                2 => record.mut_contractCreateResult().set_gasUsed(fee),
+                _ => {}
+            }
+
+            let record = TransactionRecord::try_from(record)
+                .map_err(|error| TestCaseError::fail(error.to_string()))?;
+
+            prop_assert_eq!(record.transaction_fee, fee);
+            prop_assert_eq!(record.consensus_timestamp.is_some(), consensus.is_some());
+
+            if let TransactionRecordBody::Incomplete = record.body {
+                prop_assert_eq!(body, 3);
+            } else {
+                prop_assert!(body < 3);
+            }
+        }
+
+        #[test]
+        fn test_any_record_bytes_convert(bytes in vec(any::<u8>(), 0..512)) {
+            let record = protobuf::parse_from_bytes::<proto::TransactionRecord::TransactionRecord>(
+                &bytes,
+            );
+
+            if let Ok(record) = record {
+                // the conversion may fail, but must not panic
+                let _ = TransactionRecord::try_from(record);
+            }
+        }
+    }
+}
@@ -0,0 +1,152 @@
+//! A wallet: one account and its key, with what an app needs to hold and move hbar without
+//! building transactions and queries itself.
+//!
+//! Create a new account with `Wallet::create` (keeping the mnemonic it returns), or open an
+//! existing one with `Wallet::from_mnemonic`.
+
+use crate::{
+    summary, AccountId, Client, ErrorKind, PublicKey, SecretKey, Status, TransactionReceipt,
+    TransactionRecord,
+};
+use failure::Error;
+use futures::Future;
+
+/// An account and its secret key. Everything the wallet does is paid for by, and signed as,
+/// its account.
+pub struct Wallet {
+    client: Client,
+    account: AccountId,
+    secret: SecretKey,
+}
+
+impl Wallet {
+    /// Open the wallet of `account`, whose key is `secret`. It shares the connections to the
+    /// network of `client`.
+    pub fn new(client: &Client, account: AccountId, secret: SecretKey) -> Self {
+        let mut client = client.clone();
+        let operator = secret.clone();
+
+        client.set_operator(account, move || operator.clone());
+
+        Self {
+            client,
+            account,
+            secret,
+        }
+    }
+
+    /// Open the wallet of `account` from the mnemonic (and password) its key was generated
+    /// with.
+    pub fn from_mnemonic(
+        client: &Client,
+        account: AccountId,
+        mnemonic: &str,
+        password: &str,
+    ) -> Result<Self, Error> {
+        Ok(Self::new(client, account, SecretKey::from_mnemonic(mnemonic, password)?))
+    }
+
+    /// Generate a key and create an account for it holding `initial_balance` tinybar, paid
+    /// for by the operator of `client`.
+    ///
+    /// Returns the wallet with the mnemonic of its key; the mnemonic and `password` are
+    /// needed to open the wallet again.
+    pub fn create(
+        client: &Client,
+        initial_balance: u64,
+        password: &str,
+    ) -> Result<(Self, String), Error> {
+        crate::RUNTIME
+            .lock()
+            .block_on(Self::create_async(client, initial_balance, password))
+    }
+
+    pub fn create_async(
+        client: &Client,
+        initial_balance: u64,
+        password: &str,
+    ) -> impl Future<Output = Result<(Self, String), Error>> {
+        let client = client.clone();
+        let (secret, mnemonic) = SecretKey::generate(password);
+        let mut create = client.create_account();
+
+        create.key(secret.public()).initial_balance(initial_balance);
+
+        async move {
+            let id = create.execute_async().await?;
+            let receipt = client.transaction(id).wait_for_receipt_async().await?;
+
+            if receipt.status != Status::Success {
+                Err(ErrorKind::ReceiptStatus(receipt.status))?;
+            }
+
+            Ok((Self::new(&client, receipt.account_id()?, secret), mnemonic))
+        }
+    }
+
+    #[inline]
+    pub fn account_id(&self) -> AccountId {
+        self.account
+    }
+
+    #[inline]
+    pub fn public_key(&self) -> PublicKey {
+        self.secret.public()
+    }
+
+    /// The balance of the account, in tinybar.
+    pub fn balance(&self) -> Result<u64, Error> {
+        crate::RUNTIME.lock().block_on(self.balance_async())
+    }
+
+    pub fn balance_async(&self) -> impl Future<Output = Result<u64, Error>> {
+        let mut balance = self.client.account(self.account).balance();
+
+        async move { balance.get_async().await }
+    }
+
+    /// The balance of the account in hbar, formatted for display, e.g. `12.5 ℏ`.
+    pub fn balance_hbar(&self) -> Result<String, Error> {
+        Ok(summary::hbar(self.balance()?))
+    }
+
+    /// Send `amount` tinybar to `to` and wait for the transfer to reach consensus, failing
+    /// unless it succeeded.
+    pub fn send(&self, to: AccountId, amount: i64) -> Result<TransactionReceipt, Error> {
+        crate::RUNTIME.lock().block_on(self.send_async(to, amount))
+    }
+
+    pub fn send_async(
+        &self,
+        to: AccountId,
+        amount: i64,
+    ) -> impl Future<Output = Result<TransactionReceipt, Error>> {
+        let client = self.client.clone();
+        let mut transfer = client.transfer_crypto();
+
+        transfer.transfer(self.account, -amount).transfer(to, amount);
+
+        async move {
+            let id = transfer.execute_async().await?;
+            let receipt = client.transaction(id).wait_for_receipt_async().await?;
+
+            if receipt.status != Status::Success {
+                Err(ErrorKind::ReceiptStatus(receipt.status))?;
+            }
+
+            Ok(receipt)
+        }
+    }
+
+    /// The records of the recent transactions of the account, i.e. those that moved more
+    /// hbar than its send or receive record threshold.
+    pub fn history(&self) -> Result<Vec<TransactionRecord>, Error> {
+        crate::RUNTIME.lock().block_on(self.history_async())
+    }
+
+    pub fn history_async(&self) -> impl Future<Output = Result<Vec<TransactionRecord>, Error>> {
+        let mut records = self.client.account(self.account).records();
+
+        async move { records.get_async().await }
+    }
+}
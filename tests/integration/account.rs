@@ -0,0 +1,27 @@
+use crate::common::{client, create_account, delete_account, success};
+use failure::Error;
+
+#[test]
+fn test_create_transfer_delete() -> Result<(), Error> {
+    let client = client()?;
+    let operator = client.operator_id().unwrap();
+    let (account, secret) = create_account(&client, 100_000_000)?;
+
+    assert_eq!(client.account(account).balance().get()?, 100_000_000);
+
+    let id = client
+        .transfer_crypto()
+        .transfer(operator, -1_000)
+        .transfer(account, 1_000)
+        .memo("[hedera-sdk-rust][integration] transfer")
+        .execute()?;
+
+    success(&client, id)?;
+
+    assert_eq!(client.account(account).balance().get()?, 100_001_000);
+
+    let info = client.account(account).info().get()?;
+    assert_eq!(info.key, secret.public());
+
+    delete_account(&client, account, &secret)
+}
@@ -0,0 +1,86 @@
+use failure::{format_err, Error};
+use hedera::{
+    AccountId, Client, NodeAddress, SecretKey, Status, TransactionId, TransactionReceipt,
+};
+use std::env;
+
+const DEFAULT_NODE: &str = "0:0:3@127.0.0.1:50211";
+const DEFAULT_OPERATOR: &str = "0:0:2";
+
+pub fn operator_secret() -> Result<SecretKey, Error> {
+    env::var("HEDERA_OPERATOR_SECRET")?.parse()
+}
+
+pub fn client() -> Result<Client, Error> {
+    let node = env::var("HEDERA_NODE").unwrap_or_else(|_| DEFAULT_NODE.to_owned());
+    let mut parts = node.splitn(2, '@');
+
+    let (account, endpoint) = match (parts.next(), parts.next()) {
+        (Some(account), Some(endpoint)) => (account.parse()?, endpoint.parse()?),
+        _ => Err(format_err!(
+            "HEDERA_NODE must be `{{account}}@{{host}}:{{port}}`"
+        ))?,
+    };
+
+    let operator: AccountId = env::var("HEDERA_OPERATOR")
+        .unwrap_or_else(|_| DEFAULT_OPERATOR.to_owned())
+        .parse()?;
+
+    // fail early (rather than on the first transaction) if the secret is missing
+    operator_secret()?;
+
+    Client::builder()
+        .node(NodeAddress::new(account, endpoint))
+        .operator(operator, operator_secret)
+        .build()
+}
+
+/// Wait for the receipt of `id` and check that the transaction succeeded.
+pub fn success(client: &Client, id: TransactionId) -> Result<TransactionReceipt, Error> {
+    let receipt = client.transaction(id).wait_for_receipt()?;
+
+    if receipt.status != Status::Success {
+        Err(format_err!(
+            "transaction {} failed with status {:?}",
+            id,
+            receipt.status
+        ))?;
+    }
+
+    Ok(receipt)
+}
+
+/// Create an account with a new key, paid for by the operator.
+pub fn create_account(client: &Client, balance: u64) -> Result<(AccountId, SecretKey), Error> {
+    let (secret, _) = SecretKey::generate("");
+
+    let id = client
+        .create_account()
+        .key(secret.public())
+        .initial_balance(balance)
+        .memo("[hedera-sdk-rust][integration] create_account")
+        .execute()?;
+
+    let account = success(client, id)?.account_id.unwrap();
+
+    Ok((*account, secret))
+}
+
+/// Delete an account from `create_account`, returning its balance to the operator.
+pub fn delete_account(
+    client: &Client,
+    account: AccountId,
+    secret: &SecretKey,
+) -> Result<(), Error> {
+    let operator = client
+        .operator_id()
+        .ok_or_else(|| format_err!("the client has no operator"))?;
+
+    let mut delete = client.account(account).delete();
+    delete.transfer_to(operator);
+
+    let id = delete.sign(secret).execute()?;
+    success(client, id)?;
+
+    Ok(())
+}
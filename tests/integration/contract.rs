@@ -0,0 +1,52 @@
+use crate::common::{client, operator_secret, success};
+use failure::Error;
+use std::time::Duration;
+
+// The smallest contract there is: the constructor returns empty code
+// (PUSH1 0x00 PUSH1 0x00 RETURN), hex-encoded as the network expects in the file
+const BYTECODE: &[u8] = b"60006000f3";
+
+#[test]
+fn test_create_delete() -> Result<(), Error> {
+    let client = client()?;
+    let secret = operator_secret()?;
+    let operator = client.operator_id().unwrap();
+
+    let id = client
+        .create_file()
+        .expires_in(Duration::from_secs(2_592_000))
+        .key(secret.public())
+        .contents(BYTECODE.to_vec())
+        .sign(&secret)
+        .execute()?;
+
+    let file = *success(&client, id)?.file_id.unwrap();
+
+    let id = client
+        .create_contract()
+        .file(file)
+        .gas(1_000)
+        .admin_key(secret.public())
+        .auto_renew_period(Duration::from_secs(7_890_000))
+        .memo("[hedera-sdk-rust][integration] create_contract")
+        .sign(&secret)
+        .execute()?;
+
+    let contract = *success(&client, id)?.contract_id.unwrap();
+
+    let info = client.contract(contract).info().get()?;
+    assert_eq!(info.contract_id, contract);
+
+    let id = client
+        .delete_contract(contract)
+        .obtainer_account(operator)
+        .sign(&secret)
+        .execute()?;
+
+    success(&client, id)?;
+
+    let id = client.file(file).delete().sign(&secret).execute()?;
+    success(&client, id)?;
+
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use crate::common::{client, operator_secret, success};
+use failure::Error;
+use std::time::Duration;
+
+#[test]
+fn test_create_append_delete() -> Result<(), Error> {
+    let client = client()?;
+    let secret = operator_secret()?;
+
+    let id = client
+        .create_file()
+        .expires_in(Duration::from_secs(2_592_000))
+        .key(secret.public())
+        .contents(b"Hello, ".to_vec())
+        .memo("[hedera-sdk-rust][integration] create_file")
+        .sign(&secret)
+        .execute()?;
+
+    let file = *success(&client, id)?.file_id.unwrap();
+
+    let id = client
+        .file(file)
+        .append(b"world!".to_vec())
+        .sign(&secret)
+        .execute()?;
+    success(&client, id)?;
+
+    assert_eq!(client.file(file).get()?.contents, b"Hello, world!");
+
+    let id = client.file(file).delete().sign(&secret).execute()?;
+    success(&client, id)?;
+
+    assert!(client.file(file).info().get()?.deleted);
+
+    Ok(())
+}
@@ -0,0 +1,23 @@
+//! End-to-end tests against a running network, e.g. a local one.
+//!
+//! These are only built with the `integration-tests` feature:
+//!
+//! ```text
+//! HEDERA_OPERATOR_SECRET=302e... cargo test --features integration-tests --test integration
+//! ```
+//!
+//! The network is configured through the environment:
+//!
+//!  - `HEDERA_NODE`: the node to send requests to, as `{account}@{host}:{port}`
+//!    (default `0:0:3@127.0.0.1:50211`)
+//!  - `HEDERA_OPERATOR`: the account that pays for the tests (default `0:0:2`)
+//!  - `HEDERA_OPERATOR_SECRET`: the secret key of the operator
+//!
+//! Each test deletes the entities it created once it is done with them.
+
+#![cfg(feature = "integration-tests")]
+
+mod account;
+mod common;
+mod contract;
+mod file;